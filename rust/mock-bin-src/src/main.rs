@@ -1,5 +1,8 @@
 //! Simple mock binary that when built produces an ELF with a `.defmt` section containing the log strings below
 //! If this changes, mock.rs will likely need changing since it depends on an specific version of the ELF
+//!
+//! `Mock::get_dbg` fabricates frames by index rather than by running this firmware, so the six
+//! `defmt::*!` calls below must stay in the same order and count as `DEFMT_START`/`DEFMT_END` there.
 #![no_std]
 #![no_main]
 
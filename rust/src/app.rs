@@ -1,3 +1,5 @@
+use crate::clock::{SimClock, SimTime};
+use crate::console::Console;
 use crate::notifications::Notifications;
 use crate::rtc::Rtc;
 use crate::thermal::Thermal;
@@ -32,8 +34,9 @@ pub(crate) trait Module {
     /// The module's title.
     fn title(&self) -> &'static str;
 
-    /// Update the module.
-    fn update(&mut self);
+    /// Update the module. `now` is the current point in simulation time, used by modules that
+    /// sample time-based [`Source`] data (e.g. [`Battery`]'s BST ramp).
+    fn update(&mut self, now: SimTime);
 
     /// Handle input event.
     fn handle_event(&mut self, evt: &Event);
@@ -63,14 +66,25 @@ enum SelectedTab {
 }
 
 /// The main application which holds the state and logic of the application.
+///
+/// Not generic over a separate "sync" vs "async" flavor: [`crate::source_async::AsyncSource`] is
+/// blanket-implemented for every `S: Source + Clone + Send + 'static`, so `App` only ever drives
+/// the one `S` it's given, and modules that want their reads off the render path (e.g.
+/// [`Battery`], [`Console`](crate::console::Console)) poll them through
+/// [`AsyncPoll`](crate::source_async::AsyncPoll) internally instead of `App` juggling two modes.
 pub struct App<S: Source> {
     state: AppState,
     selected_tab: SelectedTab,
     modules: BTreeMap<SelectedTab, Box<dyn Module>>,
+    /// Simulation clock driving time-based [`Source`] sampling (e.g. [`crate::mock::Mock`]'s
+    /// waveforms), advanced once per tick and independently pausable/accelerable.
+    clock: SimClock,
+    /// Debugger-style command overlay for scripted, reproducible interaction with `source`.
+    console: Console<S>,
     phantom: PhantomData<S>,
 }
 
-impl<S: Source + Clone + 'static> App<S> {
+impl<S: Source + Clone + Send + 'static> App<S> {
     /// Construct a new instance of [`App`].
     pub fn new(source: S, notifications: &Notifications) -> Self {
         let mut modules: BTreeMap<SelectedTab, Box<dyn Module>> = BTreeMap::new();
@@ -78,6 +92,7 @@ impl<S: Source + Clone + 'static> App<S> {
 
         let thermal_source = Rc::clone(&source);
         let battery_source = Rc::clone(&source);
+        let console_source = Rc::clone(&source);
 
         modules.insert(
             SelectedTab::TabThermal,
@@ -94,11 +109,19 @@ impl<S: Source + Clone + 'static> App<S> {
             state: Default::default(),
             selected_tab: Default::default(),
             modules,
+            clock: SimClock::new(),
+            console: Console::new(console_source.borrow().clone()),
             phantom: PhantomData,
         }
     }
 
-    /// Run the application's main loop.
+    /// Run the application's main loop against a native terminal.
+    ///
+    /// This is a thin driver over [`Self::tick`]/[`Self::on_event`]/the [`Widget`] impl: it owns
+    /// the only bits that are inherently tied to a real TTY (polling stdin with a timeout,
+    /// measuring wall-clock time via [`Instant`]). A non-terminal host — e.g. `web::run`'s
+    /// `requestAnimationFrame` loop — drives the same three calls from its own event/time source.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         let tick_rate = Duration::from_millis(1000);
         let mut last_tick = Instant::now();
@@ -111,11 +134,11 @@ impl<S: Source + Clone + 'static> App<S> {
 
             // Handle event if we got it, and only update tab states if we timed out
             if event::poll(timeout)? {
-                self.handle_events()?;
+                self.on_event(event::read()?);
             }
 
             if last_tick.elapsed() >= tick_rate {
-                self.update_tabs();
+                self.tick(last_tick.elapsed());
                 last_tick = Instant::now();
             }
         }
@@ -123,21 +146,59 @@ impl<S: Source + Clone + 'static> App<S> {
         Ok(())
     }
 
-    fn handle_events(&mut self) -> std::io::Result<()> {
-        let evt = event::read()?;
-        if let Event::Key(key) = evt {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('l') | KeyCode::Right => self.next_tab(),
-                    KeyCode::Char('h') | KeyCode::Left => self.previous_tab(),
-                    KeyCode::Char('q') | KeyCode::Esc => self.quit(),
+    /// Advance the sim clock by `real_dt` of wall-clock time and run one round of per-tab
+    /// updates plus breakpoint polling. Backend-neutral: takes the elapsed time as a plain
+    /// [`Duration`] rather than reading it itself, so callers with no [`Instant`] of their own
+    /// (e.g. a `wasm32` host timing off `Performance::now`) can still drive it.
+    pub(crate) fn tick(&mut self, real_dt: Duration) {
+        let now = self.clock.tick(real_dt);
+        self.update_tabs(now);
+        self.console.check_breakpoints(now, &mut self.clock);
+    }
 
-                    // Let the current tab handle event in this case
-                    _ => self.handle_tab_event(&evt),
+    /// Handle a single already-read input [`Event`]. Backend-neutral: native `run` sources events
+    /// from `crossterm::event::read`, while a non-terminal host (e.g. `web`) can construct the
+    /// same [`Event`]/[`KeyCode`] values from its own keyboard/mouse input and hand them here.
+    pub(crate) fn on_event(&mut self, evt: Event) {
+        // While the console is open, it owns all key input except the key that closes it
+        if self.console.is_visible() {
+            if let Event::Key(key) = &evt
+                && key.kind == KeyEventKind::Press
+            {
+                match key.code {
+                    KeyCode::Esc => self.console.hide(),
+                    KeyCode::Enter => self.console.submit(),
+                    _ => self.console.handle_event(&evt),
                 }
             }
+            return;
+        }
+
+        match evt {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('l') | KeyCode::Right => self.next_tab(),
+                KeyCode::Char('h') | KeyCode::Left => self.previous_tab(),
+                KeyCode::Char('q') | KeyCode::Esc => self.quit(),
+
+                // Simulation clock controls: pause/resume and 1x/2x/4x/8x playback rate
+                KeyCode::Char(' ') => self.clock.toggle_pause(),
+                KeyCode::Char('1') => self.clock.set_rate(1.0),
+                KeyCode::Char('2') => self.clock.set_rate(2.0),
+                KeyCode::Char('3') => self.clock.set_rate(4.0),
+                KeyCode::Char('4') => self.clock.set_rate(8.0),
+
+                // Open the command console
+                KeyCode::Char(':') => self.console.show(),
+
+                // Let the current tab handle event in this case
+                _ => self.handle_tab_event(&evt),
+            },
+
+            // Mouse events (e.g. wheel scrolling) are only meaningful to the current tab
+            Event::Mouse(_) => self.handle_tab_event(&evt),
+
+            _ => {}
         }
-        Ok(())
     }
 
     fn handle_tab_event(&mut self, evt: &Event) {
@@ -147,9 +208,9 @@ impl<S: Source + Clone + 'static> App<S> {
             .handle_event(evt);
     }
 
-    fn update_tabs(&mut self) {
+    fn update_tabs(&mut self, now: SimTime) {
         for module in self.modules.values_mut() {
-            module.update();
+            module.update(now);
         }
     }
 
@@ -165,6 +226,12 @@ impl<S: Source + Clone + 'static> App<S> {
         self.state = AppState::Quitting;
     }
 
+    /// Whether the app's main loop should keep running, for hosts (e.g. `web::run`) that drive
+    /// their own loop instead of calling [`Self::run`].
+    pub(crate) fn is_running(&self) -> bool {
+        self.state == AppState::Running
+    }
+
     fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
         let titles = SelectedTab::iter().map(SelectedTab::title);
         let highlight_style = (Color::default(), self.selected_tab.palette().c700);
@@ -190,8 +257,9 @@ impl<S: Source + Clone + 'static> App<S> {
 impl<S: Source + 'static> Widget for &App<S> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         use Constraint::{Length, Min};
-        let vertical = Layout::vertical([Length(1), Min(0), Length(1)]);
-        let [header_area, inner_area, footer_area] = vertical.areas(area);
+        let console_height = if self.console.is_visible() { 3 } else { 0 };
+        let vertical = Layout::vertical([Length(1), Min(0), Length(console_height), Length(1)]);
+        let [header_area, inner_area, console_area, footer_area] = vertical.areas(area);
 
         let horizontal = Layout::horizontal([Min(0), Length(20)]);
         let [tabs_area, title_area] = horizontal.areas(header_area);
@@ -199,12 +267,19 @@ impl<S: Source + 'static> Widget for &App<S> {
         render_title(title_area, buf);
         self.render_tabs(tabs_area, buf);
         self.render_selected_tab(inner_area, buf);
+        if self.console.is_visible() {
+            self.console.render(console_area, buf);
+        }
         render_footer(footer_area, buf);
     }
 }
 
+// Restoring a real terminal only makes sense when there is one; the `wasm32` frontend never
+// puts a TTY into raw mode in the first place, so it has nothing to undo here.
+#[cfg(not(target_arch = "wasm32"))]
 impl<S: Source> Drop for App<S> {
     fn drop(&mut self) {
+        let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture);
         ratatui::restore();
     }
 }
@@ -230,7 +305,7 @@ fn render_title(area: Rect, buf: &mut Buffer) {
 }
 
 fn render_footer(area: Rect, buf: &mut Buffer) {
-    Line::raw("◄ ► to change tab | Press q to quit")
+    Line::raw("◄ ► tab | Space pause | 1-4 speed | : console | q quit")
         .centered()
         .render(area, buf);
 }
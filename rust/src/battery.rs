@@ -2,7 +2,10 @@ use std::borrow::Cow;
 
 use crate::Source;
 use crate::app::Module;
+use crate::clock::SimTime;
 use crate::common;
+use crate::notifications::{self, EventStream, Notifications};
+use crate::source_async::{AsyncPoll, AsyncSource};
 use crate::widgets::battery;
 use color_eyre::{Report, Result, eyre::eyre};
 
@@ -25,6 +28,13 @@ const BATGAUGE_COLOR_LOW: Color = tailwind::RED.c500;
 const LABEL_COLOR: Color = tailwind::SLATE.c200;
 const MAX_SAMPLES: usize = 60;
 
+// Below this many points a least-squares fit is too noisy to trust, so the runtime estimate
+// falls back to the instantaneous `rate` instead.
+const MIN_SAMPLES_FOR_FIT: usize = 3;
+// Treat anything shallower than this (capacity unit/min) as a flat line rather than dividing by
+// a near-zero slope and reporting a wildly large or infinite runtime.
+const MIN_SLOPE_PER_MIN: f64 = 0.01;
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum ChargeState {
     #[default]
@@ -204,31 +214,38 @@ pub struct Battery<S: Source> {
     bst_data: BstData,
     bix_data: BixData,
     state: BatteryState,
-    t_sec: usize,
     t_min: usize,
     source: S,
+    trippoint_rx: EventStream<()>,
+    // Polled once per tick rather than calling `source.get_bst` directly, so a slow transport
+    // (e.g. real serial I/O) only ever delays a fresh reading instead of stalling the render loop.
+    bst_poll: AsyncPoll<Result<BstData>>,
 }
 
-impl<S: Source> Module for Battery<S> {
+impl<S: Source + Clone + Send + 'static> Module for Battery<S> {
     fn title(&self) -> Cow<'static, str> {
         "Battery Information".into()
     }
 
-    fn update(&mut self) {
-        if let Ok(bst_data) = self.source.get_bst() {
-            self.bst_data = bst_data;
-            self.state.bst_success = true;
-        } else {
-            self.state.bst_success = false;
+    fn update(&mut self, now: SimTime) {
+        let source = self.source.clone();
+        if let Some(result) = self.bst_poll.poll(move || AsyncSource::get_bst(&source, now)) {
+            match result {
+                Ok(bst_data) => {
+                    self.bst_data = bst_data;
+                    self.state.bst_success = true;
+                }
+                Err(_) => self.state.bst_success = false,
+            }
         }
 
-        // In mock demo, update graph every second, but real-life update every minute
+        // In mock demo, update graph every second, but real-life only on a trippoint-crossed
+        // notification instead of polling `get_bst` every second for a capacity change.
         #[cfg(feature = "mock")]
         let update_graph = true;
         #[cfg(not(feature = "mock"))]
-        let update_graph = (self.t_sec % 60) == 0;
+        let update_graph = self.drain_trippoint_events();
 
-        self.t_sec += 1;
         if update_graph {
             self.state.samples.insert(self.bst_data.capacity);
             self.t_min += 1;
@@ -260,16 +277,22 @@ impl<S: Source> Module for Battery<S> {
     }
 }
 
-impl<S: Source> Battery<S> {
-    pub fn new(source: S) -> Self {
+impl<S: Source + Clone + Send + 'static> Battery<S> {
+    pub fn new(source: S, notifications: &Notifications) -> Self {
+        let trippoint_rx = notifications
+            .subscribe(|event| matches!(event, notifications::Event::BatteryTrippoint), |_event| ())
+            .into_stream();
+
         let mut inst = Self {
             bst_data: Default::default(),
             bix_data: Default::default(),
             state: Default::default(),
-            t_sec: Default::default(),
             t_min: Default::default(),
             source,
+            trippoint_rx,
+            bst_poll: AsyncPoll::default(),
         };
+        inst.trippoint_rx.start();
 
         // This shouldn't change because BIX info is static so just read once
         if let Ok(bix_data) = inst.source.get_bix() {
@@ -279,10 +302,25 @@ impl<S: Source> Battery<S> {
             inst.state.bix_success = false;
         }
 
-        inst.update();
+        inst.update(SimTime::START);
         inst
     }
 
+    /// Drains any pending `BatteryTrippoint` notifications, returning whether at least one
+    /// arrived since the last call. Used in place of polling `get_bst` every second so the graph
+    /// only samples when the EC actually reports a capacity trippoint crossed.
+    #[cfg(not(feature = "mock"))]
+    fn drain_trippoint_events(&mut self) -> bool {
+        let mut crossed = false;
+        let trippoint_rx = &mut self.trippoint_rx;
+        notifications::poll_once(async {
+            while (trippoint_rx.next().await).is_some() {
+                crossed = true;
+            }
+        });
+        crossed
+    }
+
     fn render_info(&self, area: Rect, buf: &mut Buffer) {
         let [bix_area, status_area] = common::area_split(area, Direction::Horizontal, 50, 50);
         let [bst_area, btp_area] = common::area_split(status_area, Direction::Vertical, 70, 30);
@@ -295,6 +333,9 @@ impl<S: Source> Battery<S> {
     }
 
     fn render_bst_chart(&self, area: Rect, buf: &mut Buffer) {
+        // A dashed projection series showing the time-to-empty/-full fit alongside the sampled
+        // capacity would need `common::Graph` to accept more than one series - leaving that for
+        // a follow-up, `create_status` below surfaces the same estimate as text for now.
         let y_labels = [
             "0".bold(),
             Span::styled(
@@ -431,9 +472,79 @@ impl<S: Source> Battery<S> {
                 power_unit.as_capacity_str()
             )),
             Line::raw(format!("Present Voltage:     {} mV", self.bst_data.voltage)),
+            Line::raw(match self.bst_data.state {
+                ChargeState::Discharging => {
+                    format!("Time to Empty:       {}", Self::format_runtime(self.estimate_runtime_min()))
+                }
+                ChargeState::Charging => {
+                    format!("Time to Full:        {}", Self::format_runtime(self.estimate_runtime_min()))
+                }
+            }),
         ]
     }
 
+    /// Least-squares slope (capacity unit per minute) of the capacity-vs-time sample window, or
+    /// `None` when there aren't enough points yet to trust a fit.
+    fn capacity_slope_per_min(&self) -> Option<f64> {
+        let points = self.state.samples.get();
+        if points.len() < MIN_SAMPLES_FOR_FIT {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let sum_x: f64 = points.iter().map(|(x, _)| x).sum();
+        let sum_y: f64 = points.iter().map(|(_, y)| y).sum();
+        let sum_xy: f64 = points.iter().map(|(x, y)| x * y).sum();
+        let sum_xx: f64 = points.iter().map(|(x, _)| x * x).sum();
+
+        let denom = n * sum_xx - sum_x * sum_x;
+        if denom.abs() < f64::EPSILON {
+            return None;
+        }
+
+        Some((n * sum_xy - sum_x * sum_y) / denom)
+    }
+
+    /// Minutes until empty (discharging) or full (charging), preferring a least-squares fit of
+    /// the capacity sample window and falling back to the instantaneous ACPI `rate` when the
+    /// window is too short/flat to fit, or its slope's sign contradicts the charge state (a
+    /// noisy fit). `None` ("unknown") only once the rate fallback is also unusable (zero rate).
+    fn estimate_runtime_min(&self) -> Option<f64> {
+        let capacity = self.bst_data.capacity as f64;
+        let full = self.bix_data.last_full_capacity.max(self.bix_data.design_capacity) as f64;
+
+        let rate_fallback = || {
+            (self.bst_data.rate != 0).then(|| {
+                let rate_per_min = self.bst_data.rate as f64 / 60.0;
+                match self.bst_data.state {
+                    ChargeState::Discharging => capacity / rate_per_min,
+                    ChargeState::Charging => (full - capacity) / rate_per_min,
+                }
+            })
+        };
+
+        match self.capacity_slope_per_min() {
+            Some(slope) if slope.abs() >= MIN_SLOPE_PER_MIN => match self.bst_data.state {
+                ChargeState::Discharging if slope < 0.0 => Some(capacity / -slope),
+                ChargeState::Charging if slope > 0.0 => Some((full - capacity) / slope),
+                // Slope sign contradicts the charge state (a noisy fit), so the fit can't be
+                // trusted - fall back to the instantaneous rate rather than giving up entirely.
+                _ => rate_fallback(),
+            },
+            _ => rate_fallback(),
+        }
+    }
+
+    fn format_runtime(minutes: Option<f64>) -> String {
+        match minutes {
+            Some(m) if m.is_finite() && m >= 0.0 => {
+                let total_min = m.round() as u64;
+                format!("{}h {:02}m", total_min / 60, total_min % 60)
+            }
+            _ => "unknown".to_string(),
+        }
+    }
+
     fn render_bst(&self, area: Rect, buf: &mut Buffer) {
         let title = common::title_str_with_status("Battery Status", self.state.bst_success);
         let title = common::title_block(&title, 0, LABEL_COLOR);
@@ -0,0 +1,234 @@
+//! Deterministic simulation clock used to drive [`crate::mock::Mock`]'s synthetic waveforms.
+//!
+//! Rather than tying a waveform to "how many times have we been polled", every sampling method
+//! on [`Source`](crate::Source) takes an explicit [`SimTime`] and computes its value as a pure
+//! function of that absolute time. [`App`](crate::app::App) owns a [`SimClock`] that advances it
+//! once per loop tick and can pause or accelerate it, so demo runs are reproducible and scrubable
+//! instead of being implicitly tied to the app's wall-clock tick rate.
+
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+use std::time::Duration;
+
+// 128-bit arithmetic is pathologically slow on wasm32, so fall back to a `u64` there; that still
+// covers roughly 5 hours of simulated time, far beyond any demo run.
+#[cfg(not(target_arch = "wasm32"))]
+type Femtos = u128;
+#[cfg(target_arch = "wasm32")]
+type Femtos = u64;
+
+pub const FEMTOS_PER_SEC: Femtos = 1_000_000_000_000_000;
+pub const FEMTOS_PER_MILLISEC: Femtos = FEMTOS_PER_SEC / 1_000;
+pub const FEMTOS_PER_MICROSEC: Femtos = FEMTOS_PER_SEC / 1_000_000;
+
+/// A span of simulation time, stored as femtoseconds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClockDuration(Femtos);
+
+impl ClockDuration {
+    pub const ZERO: Self = Self(0);
+
+    pub const fn from_femtos(femtos: Femtos) -> Self {
+        Self(femtos)
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs as Femtos * FEMTOS_PER_SEC)
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis as Femtos * FEMTOS_PER_MILLISEC)
+    }
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Self(micros as Femtos * FEMTOS_PER_MICROSEC)
+    }
+
+    pub fn from_secs_f64(secs: f64) -> Self {
+        Self((secs * FEMTOS_PER_SEC as f64).max(0.0) as Femtos)
+    }
+
+    pub const fn as_femtos(self) -> Femtos {
+        self.0
+    }
+
+    pub fn as_secs_f64(self) -> f64 {
+        self.0 as f64 / FEMTOS_PER_SEC as f64
+    }
+}
+
+impl From<Duration> for ClockDuration {
+    fn from(d: Duration) -> Self {
+        Self::from_secs_f64(d.as_secs_f64())
+    }
+}
+
+impl Add for ClockDuration {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for ClockDuration {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl AddAssign for ClockDuration {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for ClockDuration {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 = self.0.saturating_sub(rhs.0);
+    }
+}
+
+impl Mul<f64> for ClockDuration {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::from_secs_f64(self.as_secs_f64() * rhs)
+    }
+}
+
+impl Div<f64> for ClockDuration {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::from_secs_f64(self.as_secs_f64() / rhs)
+    }
+}
+
+impl Mul<u32> for ClockDuration {
+    type Output = Self;
+    fn mul(self, rhs: u32) -> Self {
+        Self(self.0 * rhs as Femtos)
+    }
+}
+
+impl Div<u32> for ClockDuration {
+    type Output = Self;
+    fn div(self, rhs: u32) -> Self {
+        Self(self.0 / rhs as Femtos)
+    }
+}
+
+/// The ratio between two spans, e.g. `elapsed / period` for a periodic waveform's phase.
+impl Div for ClockDuration {
+    type Output = f64;
+    fn div(self, rhs: Self) -> f64 {
+        self.as_secs_f64() / rhs.as_secs_f64()
+    }
+}
+
+/// An absolute point in simulation time, measured from the start of the run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimTime(ClockDuration);
+
+impl SimTime {
+    pub const START: Self = Self(ClockDuration::ZERO);
+
+    pub fn as_secs_f64(self) -> f64 {
+        self.0.as_secs_f64()
+    }
+
+    /// Elapsed [`ClockDuration`] since the start of the run.
+    pub const fn since_start(self) -> ClockDuration {
+        self.0
+    }
+}
+
+impl Add<ClockDuration> for SimTime {
+    type Output = Self;
+    fn add(self, rhs: ClockDuration) -> Self {
+        Self(self.0 + rhs)
+    }
+}
+
+impl AddAssign<ClockDuration> for SimTime {
+    fn add_assign(&mut self, rhs: ClockDuration) {
+        self.0 += rhs;
+    }
+}
+
+impl Sub<ClockDuration> for SimTime {
+    type Output = Self;
+    fn sub(self, rhs: ClockDuration) -> Self {
+        Self(self.0 - rhs)
+    }
+}
+
+impl Sub for SimTime {
+    type Output = ClockDuration;
+    fn sub(self, rhs: Self) -> ClockDuration {
+        self.0 - rhs.0
+    }
+}
+
+/// Drives [`SimTime`] forward from real wall-clock ticks, at a configurable rate.
+///
+/// Owned by [`App`](crate::app::App); each loop iteration hands it the real elapsed [`Duration`]
+/// and asks for the resulting [`SimTime`], so sampling stays deterministic and reproducible
+/// regardless of how fast the terminal is actually ticking.
+#[derive(Debug, Clone, Copy)]
+pub struct SimClock {
+    now: SimTime,
+    rate: f64,
+    paused: bool,
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self {
+            now: SimTime::START,
+            rate: 1.0,
+            paused: false,
+        }
+    }
+}
+
+impl SimClock {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Advance the clock by `real_dt` of wall-clock time, scaled by the current rate, and
+    /// return the resulting [`SimTime`]. A no-op while paused.
+    pub fn tick(&mut self, real_dt: Duration) -> SimTime {
+        if !self.paused {
+            self.now += ClockDuration::from(real_dt) * self.rate;
+        }
+        self.now
+    }
+
+    pub fn now(&self) -> SimTime {
+        self.now
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Unconditionally pause, unlike [`Self::toggle_pause`] - for callers (e.g. a breakpoint hit)
+    /// that mean "stop the clock" and would otherwise risk *resuming* an already-paused clock.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Set the playback rate (`1.0` = real-time, `2.0` = 2x fast-forward, etc), clamped to a
+    /// sane range so the waveforms don't alias past recognition.
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate.clamp(0.125, 32.0);
+    }
+}
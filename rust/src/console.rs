@@ -0,0 +1,262 @@
+//! Debugger-style command console overlaid on [`App`](crate::app::App), letting testers script
+//! reproducible interaction with the active [`Source`] instead of only the fixed hotkeys in
+//! `App::on_event`.
+//!
+//! Modeled on a classic emulator monitor: an empty Enter re-runs the last command, entering a
+//! bare count re-runs it that many times, and `trip`/`break` arm one-shot breakpoints that pause
+//! the [`SimClock`] the next time a watched value is hit.
+
+use crate::battery::{BstData, ChargeState};
+use crate::clock::{SimClock, SimTime};
+use crate::source_async::{AsyncPoll, AsyncSource};
+use crate::{Source, Threshold};
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{Result, eyre};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::Event,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Paragraph, Widget},
+};
+use tui_input::{Input, backend::crossterm::EventHandler};
+
+#[derive(Parser)]
+#[command(name = "console-cmd", disable_help_subcommand = true)]
+struct Cmd {
+    #[command(subcommand)]
+    action: Action,
+}
+
+#[derive(Subcommand)]
+enum Action {
+    Set {
+        #[command(subcommand)]
+        what: SetCmd,
+    },
+    /// Pause the sim clock the next time temperature is at or above the given threshold
+    Trip { level: TripLevel },
+    /// Pause the sim clock the next time the BST state flips to discharging
+    Break { watch: BreakWatch },
+    Help,
+}
+
+#[derive(Subcommand)]
+enum SetCmd {
+    Rpm { value: f64 },
+    Btp { value: u32 },
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum TripLevel {
+    On,
+    Ramping,
+    Max,
+}
+
+impl From<TripLevel> for Threshold {
+    fn from(level: TripLevel) -> Self {
+        match level {
+            TripLevel::On => Threshold::On,
+            TripLevel::Ramping => Threshold::Ramping,
+            TripLevel::Max => Threshold::Max,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum BreakWatch {
+    Discharge,
+}
+
+const HELP_TEXT: &str =
+    "set rpm <value> | set btp <value> | trip on|ramping|max | break discharge | <blank>/<count> repeat";
+
+/// A one-shot watch that pauses the sim clock the next time it's satisfied.
+enum Breakpoint {
+    /// Pause once `get_temperature` is at or above `get_threshold(level)`; fires on the first
+    /// check if it already is when armed.
+    Thermal(TripLevel),
+    /// Pause once the BST state flips from charging to discharging.
+    BstDischarge,
+}
+
+/// Command console overlaid on [`App`](crate::app::App). Holds its own clone of the active
+/// [`Source`] so it can dispatch commands and poll breakpoints independent of whichever tab is
+/// currently selected.
+pub struct Console<S: Source> {
+    source: S,
+    input: Input,
+    visible: bool,
+    last_command: Option<String>,
+    repeat: u32,
+    breakpoint: Option<Breakpoint>,
+    last_bst_state: Option<ChargeState>,
+    message: Option<String>,
+    // Polled once per tick rather than calling `source.get_bst`/`get_temperature`/`get_threshold`
+    // directly, so a slow transport (e.g. real serial I/O) only ever delays a breakpoint check
+    // instead of stalling `App::tick` (which runs this unconditionally every tick).
+    bst_poll: AsyncPoll<Result<BstData>>,
+    thermal_poll: AsyncPoll<Option<(f64, f64)>>,
+}
+
+impl<S: Source> Console<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            input: Input::default(),
+            visible: false,
+            last_command: None,
+            repeat: 1,
+            breakpoint: None,
+            last_bst_state: None,
+            message: None,
+            bst_poll: AsyncPoll::default(),
+            thermal_poll: AsyncPoll::default(),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn show(&mut self) {
+        self.visible = true;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Takes the submitted line out of the input box and runs it, re-running or repeating
+    /// `last_command` per the rules described on the module.
+    pub fn submit(&mut self) {
+        let line = self.input.value_and_reset();
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            self.repeat_last(1);
+        } else if let Ok(count) = trimmed.parse::<u32>() {
+            self.repeat_last(count);
+        } else {
+            self.last_command = Some(trimmed.to_string());
+            self.repeat = 1;
+            self.run_once(trimmed.to_string());
+        }
+    }
+
+    fn repeat_last(&mut self, count: u32) {
+        let Some(cmd) = self.last_command.clone() else {
+            self.message = Some("No previous command".to_string());
+            return;
+        };
+
+        self.repeat = count;
+        for _ in 0..count {
+            self.run_once(cmd.clone());
+        }
+    }
+
+    fn run_once(&mut self, line: String) {
+        match Self::parse(&line) {
+            Ok(action) => self.dispatch(action),
+            Err(e) => self.message = Some(e.to_string()),
+        }
+    }
+
+    fn parse(line: &str) -> Result<Action> {
+        let tokens = line.split_whitespace();
+        Ok(Cmd::try_parse_from(std::iter::once("console-cmd").chain(tokens))
+            .map_err(|_| eyre!("Invalid command"))?
+            .action)
+    }
+
+    fn dispatch(&mut self, action: Action) {
+        let result = match action {
+            Action::Set { what: SetCmd::Rpm { value } } => self.source.set_rpm(value),
+            Action::Set { what: SetCmd::Btp { value } } => self.source.set_btp(value),
+            Action::Trip { level } => {
+                self.breakpoint = Some(Breakpoint::Thermal(level));
+                self.message = Some(format!("Breakpoint armed: temperature crosses {level:?}"));
+                return;
+            }
+            Action::Break { watch: BreakWatch::Discharge } => {
+                self.breakpoint = Some(Breakpoint::BstDischarge);
+                self.message = Some("Breakpoint armed: BST state -> discharging".to_string());
+                return;
+            }
+            Action::Help => {
+                self.message = Some(HELP_TEXT.to_string());
+                return;
+            }
+        };
+
+        self.message = match result {
+            Ok(()) => None,
+            Err(e) => Some(e.to_string()),
+        };
+    }
+
+    pub fn handle_event(&mut self, evt: &Event) {
+        let _ = self.input.handle_event(evt);
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        let title = match (&self.message, self.repeat) {
+            (Some(msg), _) => format!("Console <ESC> — {msg}"),
+            (None, n) if n > 1 => format!("Console <ESC> — repeated x{n}"),
+            (None, _) => "Console <ESC> — help for commands".to_string(),
+        };
+
+        let width = area.width.max(3) - 3;
+        let scroll = self.input.visual_scroll(width as usize);
+
+        Paragraph::new(self.input.value())
+            .style(Style::default().fg(Color::White))
+            .scroll((0, scroll as u16))
+            .block(Block::bordered().title(title))
+            .render(area, buf);
+    }
+}
+
+impl<S: Source + Clone + Send + 'static> Console<S> {
+    /// Poll any armed breakpoint against the current [`Source`] readings, pausing `clock` and
+    /// disarming itself the moment it's satisfied. Called once per tick from [`App`]'s run loop,
+    /// so both reads go through [`AsyncPoll`] rather than blocking `source` directly - otherwise
+    /// a slow real-hardware transport would stall every tick even with no breakpoint armed.
+    pub fn check_breakpoints(&mut self, now: SimTime, clock: &mut SimClock) {
+        let source = self.source.clone();
+        if let Some(result) = self.bst_poll.poll(move || AsyncSource::get_bst(&source, now)) {
+            let bst_state = result.ok().map(|bst| bst.state);
+
+            if matches!(self.breakpoint, Some(Breakpoint::BstDischarge))
+                && matches!(self.last_bst_state, Some(ChargeState::Charging))
+                && matches!(bst_state, Some(ChargeState::Discharging))
+            {
+                clock.pause();
+                self.message = Some("Breakpoint hit: BST state -> discharging".to_string());
+                self.breakpoint = None;
+            }
+
+            self.last_bst_state = bst_state;
+        }
+
+        if let Some(Breakpoint::Thermal(level)) = &self.breakpoint {
+            let level = *level;
+            let source = self.source.clone();
+            let result = self.thermal_poll.poll(move || async move {
+                let temp = AsyncSource::get_temperature(&source, now).await.ok()?;
+                let threshold = AsyncSource::get_threshold(&source, Threshold::from(level)).await.ok()?;
+                Some((temp, threshold))
+            });
+
+            if let Some(Some((temp, threshold))) = result
+                && temp >= threshold
+            {
+                clock.pause();
+                self.message = Some(format!("Breakpoint hit: temperature reached {temp:.1}"));
+                self.breakpoint = None;
+            }
+        }
+    }
+}
@@ -1,11 +1,12 @@
 use crate::Source;
 use crate::app::Module;
+use crate::clock::SimTime;
 use crate::common;
 use crate::notifications;
 use clap::{Parser, Subcommand};
 use color_eyre::eyre::Result;
 use color_eyre::eyre::eyre;
-use crossterm::event::{KeyCode, KeyEventKind};
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers, MouseEventKind};
 use defmt_decoder::{DecodeError, Frame, StreamDecoder, Table};
 use ratatui::{
     buffer::Buffer,
@@ -15,15 +16,19 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget},
 };
+use regex::{Regex, RegexBuilder};
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tui_input::{Input, backend::crossterm::EventHandler};
 
-type ReadFrameResult = Result<Option<Vec<Line<'static>>>>;
+type ReadFrameResult = Result<Vec<LogEntry>>;
 type DefmtDecoder<'a> = Box<dyn StreamDecoder + 'a>;
 
 const MAX_LOGS: usize = 1000;
+const MOUSE_SCROLL_LINES: usize = 5;
+const MOUSE_SCROLL_SHIFT_MULT: usize = 4;
 
 #[derive(Parser)]
 #[command(name = "dbg-cmd", disable_help_subcommand = true)]
@@ -36,15 +41,106 @@ struct Cmd {
 enum Action {
     Attach { path: String },
     Detach,
+    Filter {
+        #[command(subcommand)]
+        filter: FilterCmd,
+    },
+    /// Mirror every decoded frame to a rolling log file on disk
+    Log {
+        path: String,
+        /// Rotate once the file reaches this size, in MiB (defaults to 10 MiB)
+        max_size_mb: Option<u64>,
+    },
     Help,
 }
 
-#[derive(Default)]
+#[derive(Subcommand)]
+enum FilterCmd {
+    /// Only show logs at or above the given level
+    Level { level: LogLevel },
+    /// Only show logs whose message matches the given (case-insensitive) regex
+    Regex { pattern: String },
+    /// Remove the active filter
+    Clear,
+}
+
+/// Mirrors defmt's log levels so filtering can threshold on severity (TRACE < DEBUG < INFO < WARN < ERROR).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "TRACE" => Some(Self::Trace),
+            "DEBUG" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" => Some(Self::Warn),
+            "ERROR" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// An active `LogView` filter, set via the `filter` command.
+enum LogFilter {
+    Level(LogLevel),
+    Regex(Regex),
+}
+
+const MAX_CMD_HISTORY: usize = 200;
+const CMD_HISTORY_FILE: &str = ".ec_debug_history";
+
 struct CmdHandler {
     input: Input,
+    // Most recent entry last; persisted to `CMD_HISTORY_FILE` so it survives restarts
+    history: Vec<String>,
+    // `None` means back at the live draft; `Some(i)` means recalling `history[i]`
+    history_pos: Option<usize>,
+    draft: String,
+}
+
+impl Default for CmdHandler {
+    fn default() -> Self {
+        Self {
+            input: Input::default(),
+            history: Self::load_history(),
+            history_pos: None,
+            draft: String::new(),
+        }
+    }
 }
 
 impl CmdHandler {
+    fn load_history() -> Vec<String> {
+        fs::read_to_string(CMD_HISTORY_FILE)
+            .map(|s| s.lines().map(str::to_owned).collect())
+            .unwrap_or_default()
+    }
+
+    // Best-effort; failing to persist history shouldn't disrupt the TUI
+    fn save_history(&self) {
+        let _ = fs::write(CMD_HISTORY_FILE, self.history.join("\n"));
+    }
+
     fn parse(&mut self, line: String) -> Result<Action> {
         // TODO: Will likely need to check if the command is something that should be passed to debug service
         // As in, should differentiate between commands that affect the TUI vs affect the debug service
@@ -54,6 +150,54 @@ impl CmdHandler {
             .action)
     }
 
+    // Takes the submitted line out of the input box and records it in history
+    fn submit(&mut self) -> String {
+        let line = self.input.value_and_reset();
+        self.history_pos = None;
+        self.draft.clear();
+
+        if !line.trim().is_empty() && self.history.last().map(String::as_str) != Some(line.as_str()) {
+            self.history.push(line.clone());
+            if self.history.len() > MAX_CMD_HISTORY {
+                self.history.remove(0);
+            }
+            self.save_history();
+        }
+
+        line
+    }
+
+    fn recall_previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        let pos = match self.history_pos {
+            None => {
+                self.draft = self.input.value().to_string();
+                self.history.len() - 1
+            }
+            Some(pos) => pos.saturating_sub(1),
+        };
+
+        self.history_pos = Some(pos);
+        self.input = Input::new(self.history[pos].clone());
+    }
+
+    fn recall_next(&mut self) {
+        let Some(pos) = self.history_pos else {
+            return;
+        };
+
+        if pos + 1 < self.history.len() {
+            self.history_pos = Some(pos + 1);
+            self.input = Input::new(self.history[pos + 1].clone());
+        } else {
+            self.history_pos = None;
+            self.input = Input::new(self.draft.clone());
+        }
+    }
+
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         let width = area.width.max(3) - 3;
         let scroll = self.input.visual_scroll(width as usize);
@@ -61,7 +205,7 @@ impl CmdHandler {
         let input = Paragraph::new(self.input.value())
             .style(Style::default())
             .scroll((0, scroll as u16))
-            .block(Block::bordered().title("Command <ENTER>"));
+            .block(Block::bordered().title("Command <ENTER> (Ctrl+P/N for history)"));
         input.render(area, buf);
     }
 }
@@ -113,46 +257,77 @@ impl DefmtHandler {
 
     // Unfortunately, the provided color formatter by defmt_decoder doesn't play nicely with Ratatui
     // Hence the need for this manual formatting with color
-    fn frame2lines(f: &Frame) -> Vec<Line<'static>> {
-        let msg = format!("{} ", f.display_message());
-        let ts = f
-            .display_timestamp()
-            .map_or_else(|| " ".to_string(), |ts| format!("{ts} "));
-        let ts_len = ts.len();
-        let level = f
-            .level()
-            .map_or_else(|| " ".to_string(), |level| level.as_str().to_uppercase());
+    //
+    // We also retain the decoded level/message/timestamp alongside the rendered `Line`s so
+    // `LogView` can filter entries after the fact instead of only ever seeing pre-rendered text.
+    fn frame2entries(f: &Frame) -> Vec<LogEntry> {
+        let raw_message = f.display_message().to_string();
+        let msg = format!("{raw_message} ");
+        let timestamp = f.display_timestamp().map(|ts| ts.to_string());
+        let ts_str = timestamp.clone().map_or_else(|| " ".to_string(), |ts| format!("{ts} "));
+        let ts_len = ts_str.len();
 
         // Have to match over the string since the `Level` enum type is not re-exported
-        let level_color = Self::level_color(level.as_str());
+        let level_tag = f.level().map(|level| level.as_str().to_uppercase());
+        let level = level_tag.as_deref().and_then(LogLevel::from_tag);
+        let level_color = Self::level_color(level_tag.as_deref().unwrap_or(" "));
+
+        // Lets the debug tab open a split pane jumping straight to the line that emitted this log
+        let location = match (f.file(), f.line()) {
+            (Some(file), Some(line)) => Some(Location {
+                file: PathBuf::from(file),
+                line,
+                module: f.module().map(str::to_owned),
+            }),
+            _ => None,
+        };
 
-        let ts = Span::raw(ts);
-        let level = Span::styled(format!("{level:<7}"), Style::default().fg(level_color));
+        let ts_span = Span::raw(ts_str);
+        let level_span = Span::styled(
+            format!("{:<7}", level_tag.unwrap_or_else(|| " ".to_string())),
+            Style::default().fg(level_color),
+        );
 
         // A log can be multiple lines, but ratatui won't automatically display a newline
         // Hence the need to manually split the log and create a `Line` for each
         let msg: Vec<Span<'_>> = msg.lines().map(|m| Span::raw(m.to_owned())).collect();
 
         // The first line will always contain timestamp, level, and first line of log
-        let mut lines = vec![Line::from(vec![ts, level, msg[0].clone()])];
+        let mut entries = vec![LogEntry {
+            line: Line::from(vec![ts_span, level_span, msg[0].clone()]),
+            level,
+            message: raw_message.clone(),
+            timestamp,
+            location: location.clone(),
+        }];
 
         // If there are additional lines in the log, add them here
         // We also align it with the first line of the log, just looks nicer
+        // Continuation lines inherit the frame's metadata so a filter applies to the whole frame
         for span in msg.iter().skip(1) {
-            lines.push(Line::raw(format!("{:pad$}{span}", "", pad = ts_len + 7)));
+            entries.push(LogEntry {
+                line: Line::raw(format!("{:pad$}{span}", "", pad = ts_len + 7)),
+                level,
+                message: raw_message.clone(),
+                timestamp: entries[0].timestamp.clone(),
+                location: location.clone(),
+            });
         }
-        lines
+        entries
     }
 
+    // Bursty logging can queue up multiple frames between updates, so keep pulling complete
+    // frames out of the decoder until it reports it needs more input rather than stopping after one
     fn read_log(&mut self, raw: Vec<u8>) -> ReadFrameResult {
         self.decoder.with_dependent_mut(|_, d| d.received(&raw));
 
-        // TODO: May want to keep looping until reach EOF since we could receive multiple frames since last update
-        // However current debug service appears to guarantee only a single full frame will be sent at a time
-        match self.decoder.with_dependent_mut(|_, d| d.decode()) {
-            Ok(f) => Ok(Some(Self::frame2lines(&f))),
-            Err(DecodeError::UnexpectedEof) => Ok(None),
-            Err(DecodeError::Malformed) => Err(eyre!("Received malformed defmt packet")),
+        let mut entries = Vec::new();
+        loop {
+            match self.decoder.with_dependent_mut(|_, d| d.decode()) {
+                Ok(f) => entries.extend(Self::frame2entries(&f)),
+                Err(DecodeError::UnexpectedEof) => return Ok(entries),
+                Err(DecodeError::Malformed) => return Err(eyre!("Received malformed defmt packet")),
+            }
         }
     }
 }
@@ -173,57 +348,262 @@ impl Default for ScrollState {
     }
 }
 
+/// The source location (crate/file/line/module) that emitted a defmt log, used to drive the source view pane.
+#[derive(Clone)]
+struct Location {
+    file: PathBuf,
+    line: u32,
+    module: Option<String>,
+}
+
+/// A single displayed log row, along with the decoded metadata needed to filter it after the fact.
+#[derive(Clone)]
+struct LogEntry {
+    line: Line<'static>,
+    level: Option<LogLevel>,
+    message: String,
+    timestamp: Option<String>,
+    location: Option<Location>,
+}
+
+impl LogEntry {
+    // Plain (unstyled) "timestamp level message" text so the log file stays greppable outside the app
+    fn plain_text(&self) -> String {
+        let ts = self.timestamp.as_deref().unwrap_or("-");
+        let level = self.level.map_or("-".to_string(), |level| level.to_string());
+        format!("{ts} {level:<5} {}", self.message)
+    }
+}
+
+// Mirrors every decoded frame to disk so long capture sessions survive beyond the in-memory
+// `MAX_LOGS` ring buffer. Independent of what's currently visible/filtered in the TUI.
+// Used whenever the `log` command (or `Debug::new`'s `log_max_size`) doesn't specify its own size.
+const DEFAULT_LOG_FILE_MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+struct LogSink {
+    path: PathBuf,
+    max_size: u64,
+    size: u64,
+    file: fs::File,
+}
+
+impl LogSink {
+    fn new(path: PathBuf, max_size: u64) -> Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| eyre!("Failed to open log file {}: {e}", path.display()))?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+        Ok(Self {
+            path,
+            max_size,
+            size,
+            file,
+        })
+    }
+
+    fn write_entry(&mut self, entry: &LogEntry) -> Result<()> {
+        use std::io::Write;
+
+        let line = format!("{}\n", entry.plain_text());
+        self.file
+            .write_all(line.as_bytes())
+            .map_err(|e| eyre!("Failed to write log file: {e}"))?;
+        self.size += line.len() as u64;
+
+        if self.size >= self.max_size {
+            self.rollover()?;
+        }
+        Ok(())
+    }
+
+    // Renames the current file with an incrementing suffix (e.g. `app.log` -> `app.1.log`) and
+    // opens a fresh file at the original path
+    fn rollover(&mut self) -> Result<()> {
+        let mut suffix = 1;
+        let mut rolled = Self::suffixed_path(&self.path, suffix);
+        while rolled.exists() {
+            suffix += 1;
+            rolled = Self::suffixed_path(&self.path, suffix);
+        }
+
+        fs::rename(&self.path, &rolled).map_err(|e| eyre!("Failed to rotate log file: {e}"))?;
+        self.file = fs::File::create(&self.path).map_err(|e| eyre!("Failed to open log file: {e}"))?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn suffixed_path(path: &Path, suffix: u32) -> PathBuf {
+        match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(ext)) => {
+                path.with_file_name(format!("{}.{suffix}.{}", stem.to_string_lossy(), ext.to_string_lossy()))
+            }
+            (Some(stem), None) => path.with_file_name(format!("{}.{suffix}", stem.to_string_lossy())),
+            _ => path.to_path_buf(),
+        }
+    }
+}
+
 #[derive(Default)]
 struct LogView {
     y_scroll: ScrollState,
     x_scroll: ScrollState,
     max_log_len: usize,
-    logs: common::SampleBuf<Line<'static>, MAX_LOGS>,
+    logs: common::SampleBuf<LogEntry, MAX_LOGS>,
+    filter: Option<LogFilter>,
+    // Indices into `logs.as_vec()` that pass the active filter, recomputed on insert/filter change
+    filtered: Vec<usize>,
+    // Highlighted row driving the source view pane, distinct from (and independent of) scroll position
+    selected: Option<usize>,
 }
 
 impl LogView {
-    // Updates cached logs with newly read frame
+    // Updates cached logs with a batch of newly read frames (may be empty if the decoder is
+    // still waiting on more input, or span multiple frames if several arrived since last update)
     fn log_frame(&mut self, frame: ReadFrameResult) {
         match frame {
-            // If a full frame was received, log it
-            Ok(Some(log)) => {
-                let lines = log.len();
-                for line in log {
-                    let len = format!("{line}").len();
+            Ok(entries) if !entries.is_empty() => {
+                let visible_before = self.filtered.len();
+                for entry in entries {
+                    let len = format!("{}", entry.line).len();
                     self.max_log_len = std::cmp::max(self.max_log_len, len);
-                    self.logs.insert(line);
+                    self.logs.insert(entry);
                 }
+                self.recompute_filter();
+                // Pass how many rows the batch added to the (possibly filtered) view, so "stay pinned
+                // to bottom" still holds when many lines land at once
+                let lines = self.filtered.len().saturating_sub(visible_before);
                 self.update_scroll(lines);
             }
-            // Unless it was an error
+            // Nothing complete yet, just do nothing until we get a full frame
+            Ok(_) => {}
             // TODO: Handle recovery?
-            Err(e) => {
-                self.log_meta(e);
-            }
-            // But if was unexpected EOF, just do nothing until we get the full frame
-            _ => {}
+            Err(e) => self.log_meta(e),
         }
     }
 
     fn log_meta(&mut self, msg: impl std::fmt::Display) {
-        self.logs
-            .insert(Line::styled(format!("<{msg}>"), Style::default().fg(Color::Cyan)));
+        self.logs.insert(LogEntry {
+            line: Line::styled(format!("<{msg}>"), Style::default().fg(Color::Cyan)),
+            level: None,
+            message: msg.to_string(),
+            timestamp: None,
+            location: None,
+        });
+        self.recompute_filter();
         self.update_scroll(1);
     }
 
+    /// Set (or clear) the active filter and recompute which rows are visible.
+    fn set_filter(&mut self, filter: Option<LogFilter>) {
+        self.filter = filter;
+        self.recompute_filter();
+        self.update_scroll(0);
+    }
+
+    // Recomputes which log entries pass the active filter; meta entries (no level) always pass
+    // a level filter since they aren't decoded frames, but still have to match a regex filter.
+    fn recompute_filter(&mut self) {
+        let all = self.logs.as_vec();
+        self.filtered = match &self.filter {
+            None => (0..all.len()).collect(),
+            Some(LogFilter::Level(min)) => all
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.level.map(|level| level >= *min).unwrap_or(true))
+                .map(|(i, _)| i)
+                .collect(),
+            Some(LogFilter::Regex(re)) => all
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| re.is_match(&e.message))
+                .map(|(i, _)| i)
+                .collect(),
+        };
+
+        // Filtering can only shrink the visible set, so clamp scroll and selection back into range
+        let max_pos = self.filtered.len().saturating_sub(self.y_scroll.size as usize);
+        self.y_scroll.pos = self.y_scroll.pos.min(max_pos);
+
+        if self.filtered.is_empty() {
+            self.selected = None;
+        } else if let Some(sel) = self.selected {
+            self.selected = Some(sel.min(self.filtered.len() - 1));
+        }
+    }
+
+    /// Toggle the source-view cursor on/off, starting on the newest visible row.
+    fn toggle_select(&mut self) {
+        self.selected = match self.selected {
+            Some(_) => None,
+            None => self.filtered.len().checked_sub(1),
+        };
+    }
+
+    fn select_up(&mut self) {
+        if let Some(sel) = self.selected {
+            self.selected = Some(sel.saturating_sub(1));
+        }
+    }
+
+    fn select_down(&mut self) {
+        if let Some(sel) = self.selected {
+            self.selected = Some(sel.saturating_add(1).min(self.filtered.len().saturating_sub(1)));
+        }
+    }
+
+    /// The source location to display in the split pane for the currently selected row, if any.
+    fn selected_location(&self) -> Option<Location> {
+        let sel = self.selected?;
+        let idx = *self.filtered.get(sel)?;
+        self.logs.as_vec().get(idx)?.location.clone()
+    }
+
     fn scroll_up(&mut self) {
-        self.y_scroll.pos = self.y_scroll.pos.saturating_sub(1);
-        self.y_scroll.bar.prev();
+        self.scroll_up_by(1);
     }
 
     fn scroll_down(&mut self) {
-        if self.logs.len() > self.y_scroll.size as usize {
+        self.scroll_down_by(1);
+    }
+
+    fn scroll_up_by(&mut self, lines: usize) {
+        self.y_scroll.pos = self.y_scroll.pos.saturating_sub(lines);
+        self.y_scroll.bar = self.y_scroll.bar.position(self.y_scroll.pos);
+    }
+
+    fn scroll_down_by(&mut self, lines: usize) {
+        if self.filtered.len() > self.y_scroll.size as usize {
             self.y_scroll.pos = self
                 .y_scroll
                 .pos
-                .saturating_add(1)
-                .clamp(0, self.logs.len() - self.y_scroll.size as usize);
-            self.y_scroll.bar.next();
+                .saturating_add(lines)
+                .clamp(0, self.filtered.len() - self.y_scroll.size as usize);
+            self.y_scroll.bar = self.y_scroll.bar.position(self.y_scroll.pos);
+        }
+    }
+
+    // Move by a full page (the log pane's visible height)
+    fn page_up(&mut self) {
+        self.scroll_up_by(self.y_scroll.size as usize);
+    }
+
+    fn page_down(&mut self) {
+        self.scroll_down_by(self.y_scroll.size as usize);
+    }
+
+    fn scroll_to_top(&mut self) {
+        self.y_scroll.pos = 0;
+        self.y_scroll.bar = self.y_scroll.bar.position(0);
+    }
+
+    fn scroll_to_bottom(&mut self) {
+        if self.filtered.len() > self.y_scroll.size as usize {
+            let bottom = self.filtered.len() - self.y_scroll.size as usize;
+            self.y_scroll.pos = bottom;
+            self.y_scroll.bar = self.y_scroll.bar.position(bottom);
         }
     }
 
@@ -253,9 +633,9 @@ impl LogView {
                 .content_length(self.max_log_len - self.x_scroll.size as usize);
         }
 
-        // Adjust the length of the vertical scroll bar if the number of logs doesn't fit in the window
-        if self.logs.len() > self.y_scroll.size as usize {
-            let height = self.logs.len() - self.y_scroll.size as usize;
+        // Adjust the length of the vertical scroll bar if the number of visible logs doesn't fit in the window
+        if self.filtered.len() > self.y_scroll.size as usize {
+            let height = self.filtered.len() - self.y_scroll.size as usize;
             self.y_scroll.bar = self.y_scroll.bar.content_length(height);
 
             // If we are currently scrolled to the bottom, stay scrolled to the bottom as new logs come in
@@ -267,26 +647,60 @@ impl LogView {
     }
 
     fn display_help(&mut self) {
-        let help_lines: [&'static str; 4] = [
+        let help_lines: [&'static str; 8] = [
             "Commands supported:",
             "help (Display help)",
             "attach <elf-path> (Attach an ELF file to view defmt logs)",
             "detach (Detach ELF)",
+            "filter level <trace|debug|info|warn|error> (Only show logs at or above this level)",
+            "filter regex <pattern> (Only show logs whose message matches this regex)",
+            "filter clear (Remove the active filter)",
+            "log <path> [max-size-mb] (Mirror all decoded frames to a rolling log file, default 10 MiB)",
         ];
 
+        let count = help_lines.len();
         for line in help_lines {
-            self.logs.insert(Line::raw(line));
+            self.logs.insert(LogEntry {
+                line: Line::raw(line),
+                level: None,
+                message: line.to_string(),
+                timestamp: None,
+                location: None,
+            });
         }
-        self.update_scroll(4);
+        self.recompute_filter();
+        self.update_scroll(count);
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         // Separate this from paragraph because we need to know the inner area for proper log scrolling
-        let b = common::title_block("Logs (Use Shift + ◄ ▲ ▼ ► to scroll)", 1, Color::White);
+        let title = match &self.filter {
+            Some(LogFilter::Level(level)) => {
+                format!("Logs (filter: level >= {level}) (Use Shift + ◄ ▲ ▼ ► to scroll)")
+            }
+            Some(LogFilter::Regex(re)) => format!("Logs (filter: /{re}/) (Use Shift + ◄ ▲ ▼ ► to scroll)"),
+            None => "Logs (Use Shift + ◄ ▲ ▼ ► to scroll)".to_string(),
+        };
+        let b = common::title_block(&title, 1, Color::White);
         self.y_scroll.size = b.inner(area).height;
         self.x_scroll.size = b.inner(area).width;
 
-        Paragraph::new(self.logs.as_vec())
+        let all = self.logs.as_vec();
+        let lines: Vec<Line<'static>> = self
+            .filtered
+            .iter()
+            .enumerate()
+            .filter_map(|(row, &i)| all.get(i).map(|entry| (row, entry)))
+            .map(|(row, entry)| {
+                if self.selected == Some(row) {
+                    entry.line.clone().style(Style::default().bg(Color::DarkGray))
+                } else {
+                    entry.line.clone()
+                }
+            })
+            .collect();
+
+        Paragraph::new(lines)
             .scroll((self.y_scroll.pos as u16, self.x_scroll.pos as u16))
             .block(b)
             .render(area, buf);
@@ -303,13 +717,97 @@ impl LogView {
     }
 }
 
+// A handful of Rust keywords, enough to make the windowed source readable without pulling in a
+// full syntax-highlighting crate just for this debugger-style pane
+const RUST_KEYWORDS: &[&str] = &[
+    "fn", "let", "mut", "if", "else", "match", "struct", "enum", "impl", "pub", "use", "mod", "return", "for",
+    "while", "loop", "self", "Self", "const", "static", "async", "await", "trait", "where", "as", "in", "ref",
+    "move", "dyn", "unsafe",
+];
+
+/// Shows the source file/line that emitted the currently selected log, like a debugger's source view.
+#[derive(Default)]
+struct SourceView {
+    // Cached file contents keyed by path, so repeatedly selecting the same frame doesn't re-read disk
+    cache: HashMap<PathBuf, Option<Vec<String>>>,
+}
+
+impl SourceView {
+    fn lines(&mut self, path: &Path) -> &Option<Vec<String>> {
+        self.cache
+            .entry(path.to_path_buf())
+            .or_insert_with(|| fs::read_to_string(path).ok().map(|s| s.lines().map(str::to_owned).collect()))
+    }
+
+    // A minimal, match-based keyword highlighter in the spirit of `DefmtHandler::level_color`
+    fn highlight_line(line: &str) -> Line<'static> {
+        let spans = line
+            .split_inclusive(char::is_whitespace)
+            .map(|word| {
+                if RUST_KEYWORDS.contains(&word.trim()) {
+                    Span::styled(word.to_owned(), Style::default().fg(Color::Magenta))
+                } else {
+                    Span::raw(word.to_owned())
+                }
+            })
+            .collect::<Vec<_>>();
+        Line::from(spans)
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer, location: &Location) {
+        let title = match &location.module {
+            Some(module) => format!("Source: {module} ({}:{})", location.file.display(), location.line),
+            None => format!("Source: {}:{}", location.file.display(), location.line),
+        };
+        let block = common::title_block(&title, 1, Color::White);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(lines) = self.lines(&location.file) else {
+            Paragraph::new("<source unavailable>")
+                .style(Style::default().fg(Color::DarkGray))
+                .render(inner, buf);
+            return;
+        };
+
+        let height = inner.height as usize;
+        let target = (location.line.saturating_sub(1) as usize).min(lines.len().saturating_sub(1));
+        let last_start = lines.len().saturating_sub(height);
+        let start = target.saturating_sub(height / 2).min(last_start);
+        let end = (start + height).min(lines.len());
+
+        let gutter_width = end.to_string().len().max(3);
+        let rendered: Vec<Line<'static>> = (start..end)
+            .map(|i| {
+                let gutter = Span::styled(
+                    format!("{:>gutter_width$} ", i + 1),
+                    Style::default().fg(Color::DarkGray),
+                );
+                let mut spans = vec![gutter];
+                spans.extend(Self::highlight_line(&lines[i]).spans);
+
+                let line = Line::from(spans);
+                if i == target {
+                    line.style(Style::default().bg(Color::DarkGray))
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        Paragraph::new(rendered).render(inner, buf);
+    }
+}
+
 pub struct Debug<S: Source> {
     // Currently source is unused by main thread, but keeping it for ease of use in future
     source: S,
     log_view: LogView,
+    source_view: SourceView,
     defmt: Option<DefmtHandler>,
     cmd_handler: CmdHandler,
-    event_rx: notifications::EventRx<Result<Vec<u8>>>,
+    event_stream: notifications::EventStream<Result<Vec<u8>>>,
+    log_sink: Option<LogSink>,
 }
 
 impl<S: Source> Module for Debug<S> {
@@ -321,40 +819,110 @@ impl<S: Source> Module for Debug<S> {
         .into()
     }
 
-    fn update(&mut self) {
-        if let Some(defmt) = &mut self.defmt {
-            while let Some(data) = self.event_rx.receive() {
+    fn update(&mut self, _now: SimTime) {
+        if self.defmt.is_none() {
+            #[cfg(feature = "mock")]
+            self.update_mock_fallback();
+            return;
+        }
+
+        let Self { defmt, event_stream, log_view, log_sink, .. } = self;
+        let defmt = defmt.as_mut().expect("checked above");
+
+        // `poll_once` only returns `Some` once the `while let` below runs to completion, which
+        // only happens when the stream itself ends (the notification service is gone) rather
+        // than merely having nothing left to drain this tick.
+        let stream_ended = notifications::poll_once(async {
+            while let Some((data, dropped)) = event_stream.next().await {
+                if dropped > 0 {
+                    log_view.log_meta(format!("Dropped {dropped} notification(s), logs may be incomplete"));
+                }
+
                 match data {
                     Ok(raw) => {
                         let frame = defmt.read_log(raw);
-                        self.log_view.log_frame(frame);
+
+                        // Mirror to disk independent of what's visible/filtered in the TUI
+                        if let (Ok(entries), Some(sink)) = (&frame, &mut *log_sink) {
+                            for entry in entries {
+                                if let Err(e) = sink.write_entry(entry) {
+                                    log_view.log_meta(e);
+                                }
+                            }
+                        }
+
+                        log_view.log_frame(frame);
                     }
-                    Err(e) => self.log_view.log_meta(e),
+                    Err(e) => log_view.log_meta(e),
                 }
             }
+        });
+
+        if stream_ended.is_some() {
+            self.log_view.log_meta("Notification service is gone, detaching");
+            self.defmt = None;
+            self.event_stream.stop();
         }
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
         // Give logs area as much room as possible
-        let [logs_area, cmd_area] =
+        let [upper_area, cmd_area] =
             common::area_split_constrained(area, Direction::Vertical, Constraint::Min(0), Constraint::Max(3));
 
-        self.log_view.render(logs_area, buf);
+        // Split the log pane in half to make room for the source view only while a row is selected
+        match self.log_view.selected_location() {
+            Some(location) => {
+                let [logs_area, source_area] = common::area_split(upper_area, Direction::Horizontal, 50, 50);
+                self.log_view.render(logs_area, buf);
+                self.source_view.render(source_area, buf, &location);
+            }
+            None => self.log_view.render(upper_area, buf),
+        }
+
         self.cmd_handler.render(cmd_area, buf);
     }
 
     fn handle_event(&mut self, evt: &Event) {
+        if let Event::Mouse(mouse) = evt {
+            // 5 lines per notch, a bigger jump while Shift is held
+            let step = if mouse.modifiers.contains(KeyModifiers::SHIFT) {
+                MOUSE_SCROLL_LINES * MOUSE_SCROLL_SHIFT_MULT
+            } else {
+                MOUSE_SCROLL_LINES
+            };
+
+            match mouse.kind {
+                MouseEventKind::ScrollUp => self.log_view.scroll_up_by(step),
+                MouseEventKind::ScrollDown => self.log_view.scroll_down_by(step),
+                _ => {}
+            }
+            return;
+        }
+
         if let Event::Key(key) = evt
             && key.kind == KeyEventKind::Press
         {
             match key.code {
+                KeyCode::Up if key.modifiers.contains(KeyModifiers::CONTROL) => self.log_view.select_up(),
+                KeyCode::Down if key.modifiers.contains(KeyModifiers::CONTROL) => self.log_view.select_down(),
                 KeyCode::Up => self.log_view.scroll_up(),
                 KeyCode::Down => self.log_view.scroll_down(),
                 KeyCode::Left => self.log_view.scroll_left(),
                 KeyCode::Right => self.log_view.scroll_right(),
+                KeyCode::PageUp => self.log_view.page_up(),
+                KeyCode::PageDown => self.log_view.page_down(),
+                KeyCode::Home => self.log_view.scroll_to_top(),
+                KeyCode::End => self.log_view.scroll_to_bottom(),
+                KeyCode::Tab => self.log_view.toggle_select(),
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.cmd_handler.recall_previous()
+                }
+                KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.cmd_handler.recall_next()
+                }
                 KeyCode::Enter => {
-                    let str = self.cmd_handler.input.value_and_reset();
+                    let str = self.cmd_handler.submit();
                     self.handle_cmd(str);
                 }
                 _ => {
@@ -366,7 +934,13 @@ impl<S: Source> Module for Debug<S> {
 }
 
 impl<S: Source> Debug<S> {
-    pub fn new(source: S, elf_path: Option<PathBuf>, notifications: &notifications::Notifications) -> Self {
+    pub fn new(
+        source: S,
+        elf_path: Option<PathBuf>,
+        log_path: Option<PathBuf>,
+        log_max_size: Option<u64>,
+        notifications: &notifications::Notifications,
+    ) -> Self {
         // Sources must ensure they are thread-safe
         // Currently mock and ACPI are thread-safe
         let src = source.clone();
@@ -378,46 +952,115 @@ impl<S: Source> Debug<S> {
         // So instead the event receiver thread itself will call `get_dbg` as notifications come in and store the raw frames in a buffer
         // The debug tab can then just process every raw frame once a second and push all those to the log viewer
         // This allows for a more real-time approach of receiving logs
-        let event_rx =
-            notifications.event_receiver(notifications::Event::DbgFrameAvailable, move |_event| src.get_dbg());
+        let event_stream = notifications
+            .subscribe(
+                |event| matches!(event, notifications::Event::DbgFrameAvailable),
+                move |_event| src.get_dbg(),
+            )
+            .into_stream();
 
         let mut debug = Self {
             source,
             log_view: Default::default(),
+            source_view: Default::default(),
             defmt: None,
             cmd_handler: Default::default(),
-            event_rx,
+            event_stream,
+            log_sink: None,
         };
 
         if let Some(elf_path) = elf_path {
             debug.attach_elf(elf_path);
         } else {
+            #[cfg(not(feature = "mock"))]
             debug.detach_elf();
 
+            // With no ELF attached, `Mock`'s frames would otherwise never be shown (the event
+            // stream starts stopped, so matching events are dropped) - start the stream and decode them through the
+            // built-in fake symbol table instead, so the decoder path is exercised without
+            // requiring `attach mock-bin` first.
             #[cfg(feature = "mock")]
-            debug.log_view.log_meta("Try running the command `attach mock-bin`");
+            {
+                debug
+                    .log_view
+                    .log_meta("Showing logs decoded via Mock's built-in fake symbol table (run `attach mock-bin` for the real defmt decoder)");
+                debug.event_stream.start();
+            }
+        }
+
+        if let Some(log_path) = log_path {
+            debug.enable_log_sink(log_path, log_max_size);
         }
 
         debug
     }
 
+    fn enable_log_sink(&mut self, path: PathBuf, max_size: Option<u64>) {
+        match LogSink::new(path, max_size.unwrap_or(DEFAULT_LOG_FILE_MAX_SIZE)) {
+            Ok(sink) => {
+                self.log_view.log_meta(format!("Logging to {}", sink.path.display()));
+                self.log_sink = Some(sink);
+            }
+            Err(e) => self.log_view.log_meta(e),
+        }
+    }
+
     fn handle_cmd(&mut self, str: String) {
         match self.cmd_handler.parse(str) {
             Ok(action) => match action {
                 Action::Attach { path } => self.attach_elf(PathBuf::from(path)),
                 Action::Detach => self.detach_elf(),
+                Action::Filter { filter } => self.handle_filter_cmd(filter),
+                Action::Log { path, max_size_mb } => {
+                    self.enable_log_sink(PathBuf::from(path), max_size_mb.map(|mb| mb * 1024 * 1024))
+                }
                 Action::Help => self.log_view.display_help(),
             },
             Err(e) => self.log_view.log_meta(e),
         }
     }
 
+    fn handle_filter_cmd(&mut self, filter: FilterCmd) {
+        match filter {
+            FilterCmd::Level { level } => self.log_view.set_filter(Some(LogFilter::Level(level))),
+            FilterCmd::Regex { pattern } => match RegexBuilder::new(&pattern).case_insensitive(true).build() {
+                Ok(re) => self.log_view.set_filter(Some(LogFilter::Regex(re))),
+                Err(e) => self.log_view.log_meta(format!("Invalid regex: {e}")),
+            },
+            FilterCmd::Clear => self.log_view.set_filter(None),
+        }
+    }
+
+    // With no ELF attached, resolves `Mock`'s frames against its built-in fake symbol table
+    // instead of the real `defmt_decoder`-based path above, so the decoder path is exercised
+    // in-app without requiring `attach mock-bin` first.
+    #[cfg(feature = "mock")]
+    fn update_mock_fallback(&mut self) {
+        let Self { event_stream, log_view, .. } = self;
+
+        notifications::poll_once(async {
+            while let Some((data, dropped)) = event_stream.next().await {
+                if dropped > 0 {
+                    log_view.log_meta(format!("Dropped {dropped} notification(s), logs may be incomplete"));
+                }
+
+                match data {
+                    Ok(raw) => match crate::mock::decode_mock_frame(&raw) {
+                        Some((timestamp, message)) => log_view.log_meta(format!("{timestamp:>10}us {message}")),
+                        None => log_view.log_meta("Failed to decode mock defmt frame"),
+                    },
+                    Err(e) => log_view.log_meta(e),
+                }
+            }
+        });
+    }
+
     fn attach_elf(&mut self, elf_path: PathBuf) {
         match DefmtHandler::new(elf_path) {
             Ok(defmt) => {
                 self.log_view.log_meta(format!("Attached ELF: {}", defmt.bin_name));
                 self.defmt = Some(defmt);
-                self.event_rx.start();
+                self.event_stream.start();
 
                 // Initial read to kick off debug service (since we would've missed last notification)
                 let _ = self.source.get_dbg();
@@ -433,6 +1076,6 @@ impl<S: Source> Debug<S> {
         self.defmt = None;
         self.log_view
             .log_meta("No ELF attached so debug logs are not available");
-        self.event_rx.stop();
+        self.event_stream.stop();
     }
 }
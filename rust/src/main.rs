@@ -5,6 +5,7 @@ use ec_demo::app::{App, AppArgs};
 fn main() -> Result<()> {
     color_eyre::install()?;
     let terminal = ratatui::init();
+    crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)?;
 
     #[cfg(not(feature = "mock"))]
     let source = ec_demo::acpi::Acpi::new();
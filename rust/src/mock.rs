@@ -1,12 +1,9 @@
+use crate::clock::SimTime;
+use crate::sync::{self, AtomicI64, AtomicU16, AtomicU64, Ordering};
 use crate::{Source, Threshold, common};
 use color_eyre::Result;
-use std::sync::{
-    Mutex, OnceLock,
-    atomic::{AtomicI64, AtomicU16, AtomicU32, AtomicU64, Ordering},
-};
 
-static SET_RPM: AtomicI64 = AtomicI64::new(-1);
-static SAMPLE: OnceLock<Mutex<(i64, i64)>> = OnceLock::new();
+sync::static_atomic!(SET_RPM: AtomicI64 = AtomicI64::new(-1));
 
 // Produces a fake "on-the-wire" byte representation of a defmt call that matches format expected by mock-bin
 // Index is equal to the address of the log string in the `.defmt` section of mock-bin ELF
@@ -22,6 +19,46 @@ fn mock_defmt_wire(index: u16, timestamp: u64) -> Vec<u8> {
     buf
 }
 
+// Mirrors, in the same order, the six `defmt::*!` calls in `mock-bin-src/src/main.rs` - i.e. a
+// tiny, hand-built stand-in for the address-keyed symbol table a real `.defmt` section carries,
+// indexed the same way `mock_defmt_wire` numbers its frames (`DEFMT_START..=DEFMT_END` below).
+const FAKE_SYMBOL_TABLE: [&str; 6] = [
+    "This is a trace defmt log",
+    "This is a debug defmt log",
+    "This is a really long log message. Really really really long. Its length should be measured \
+     in light-years. Not characters. It will wrap around on all monitors not of cosmic scale. Who \
+     needs to log something this long anyway? Who knows. But someone will. Therefore we must be \
+     prepared.",
+    "This is a log message with a newline.\nSee? I'm on a newline now!",
+    "This is a warn defmt log",
+    "This is a error defmt log",
+];
+
+/// Reverses [`mock_defmt_wire`]: rzcobs-decodes the frame, splits the result back into its
+/// `(index, timestamp)` fields, and resolves `index` against [`FAKE_SYMBOL_TABLE`] - the same
+/// index -> format-string lookup a real `.defmt` section's symbol table performs, just over a
+/// six-entry table hand-written here instead of one parsed out of an ELF. Backs `Debug`'s
+/// in-app log view whenever no ELF is attached (see `debug.rs`'s `update_mock_fallback`), and is
+/// also checked to actually round-trip in `tests/mock_defmt.rs`, without needing `defmt_decoder`
+/// or a compiled `mock-bin` ELF on hand.
+pub fn decode_mock_frame(frame: &[u8]) -> Option<(u64, &'static str)> {
+    let frame = frame.strip_suffix(&[0x00])?;
+    let decoded = rzcobs::decode(frame).ok()?;
+    let index = u16::from_le_bytes(decoded.get(0..2)?.try_into().ok()?);
+    let timestamp = u64::from_le_bytes(decoded.get(2..10)?.try_into().ok()?);
+    let message = FAKE_SYMBOL_TABLE.get(usize::from(index).checked_sub(1)?)?;
+    Some((timestamp, message))
+}
+
+// Triangle wave between `min` and `max` with the given period, evaluated at absolute `now`
+// rather than stepped per call, so it's a pure function of simulation time.
+fn triangle_wave(now: SimTime, period_secs: f64, min: f64, max: f64) -> f64 {
+    let t = now.as_secs_f64().rem_euclid(period_secs);
+    let half = period_secs / 2.0;
+    let phase = if t < half { t / half } else { 2.0 - t / half };
+    min + phase * (max - min)
+}
+
 #[derive(Default, Copy, Clone)]
 pub struct Mock {}
 
@@ -32,41 +69,25 @@ impl Mock {
 }
 
 impl Source for Mock {
-    fn get_temperature(&self) -> Result<f64> {
-        let mut sample = SAMPLE.get_or_init(|| Mutex::new((2732, 1))).lock().unwrap();
-
-        sample.0 += 10 * sample.1;
-        if sample.0 >= 3232 || sample.0 <= 2732 {
-            sample.1 *= -1;
-        }
-
-        Ok(common::dk_to_c(sample.0 as u32))
+    fn get_temperature(&self, now: SimTime) -> Result<f64> {
+        // Triangle wave between 2732 and 3232 deciKelvin, 10 dK/sec, matching the original per-tick ramp
+        let dk = triangle_wave(now, 100.0, 2732.0, 3232.0);
+        Ok(common::dk_to_c(dk as u32))
     }
 
-    fn get_rpm(&self) -> Result<f64> {
+    fn get_rpm(&self, now: SimTime) -> Result<f64> {
         use std::f64::consts::PI;
-        use std::sync::{Mutex, OnceLock};
 
         // For mock, if user sets RPM, we just always return what was last set instead of sin wave
         let set_rpm = SET_RPM.load(Ordering::Relaxed);
         if set_rpm >= 0 {
             Ok(set_rpm as f64)
         } else {
-            // Generate sin wave
-            static SAMPLE: OnceLock<Mutex<f64>> = OnceLock::new();
-            let mut sample = SAMPLE.get_or_init(|| Mutex::new(0.0)).lock().unwrap();
-
-            let freq = 0.1;
+            // 0.1 rad/sec phase advance, same cadence as the original per-tick sin wave
+            let freq = 0.1 / (2.0 * PI);
             let amplitude = 3000.0;
             let base = 3000.0;
-            let rpm = (sample.sin() * amplitude) + base;
-
-            *sample += freq;
-            if *sample > 2.0 * PI {
-                *sample -= 2.0 * PI;
-            }
-
-            Ok(rpm)
+            Ok((2.0 * PI * freq * now.as_secs_f64()).sin() * amplitude + base)
         }
     }
 
@@ -91,30 +112,18 @@ impl Source for Mock {
         Ok(())
     }
 
-    fn get_bst(&self) -> Result<crate::battery::BstData> {
-        static STATE: AtomicU32 = AtomicU32::new(2);
+    fn get_bst(&self, now: SimTime) -> Result<crate::battery::BstData> {
         const MAX_CAPACITY: u32 = 10000;
-        static CAPACITY: AtomicU32 = AtomicU32::new(0);
-        const RATE: u32 = 1000;
-
-        let state = STATE.load(Ordering::Relaxed);
-        let capacity = CAPACITY.load(Ordering::Relaxed);
-        let mut new_capacity = capacity;
-
-        // We are only using atomics to satisfy borrow-checker
-        // Thus we update non-atomically for simplicity
-        if state == 2 {
-            new_capacity += RATE;
-            if new_capacity > MAX_CAPACITY {
-                STATE.store(1, Ordering::Relaxed);
-            }
+        const RATE: u32 = 1000; // units/sec
+        const PERIOD_SECS: f64 = 2.0 * MAX_CAPACITY as f64 / RATE as f64;
+
+        let t = now.as_secs_f64().rem_euclid(PERIOD_SECS);
+        let half = PERIOD_SECS / 2.0;
+        let (state, capacity) = if t < half {
+            (2, (t / half * MAX_CAPACITY as f64) as u32)
         } else {
-            new_capacity -= RATE;
-            if new_capacity < RATE {
-                STATE.store(2, Ordering::Relaxed);
-            }
-        }
-        CAPACITY.store(new_capacity.clamp(0, MAX_CAPACITY), Ordering::Relaxed);
+            (1, ((1.0 - (t - half) / half) * MAX_CAPACITY as f64) as u32)
+        };
 
         Ok(crate::battery::BstData {
             state: crate::battery::ChargeState::try_from(state)?,
@@ -156,10 +165,13 @@ impl Source for Mock {
     }
 
     fn get_dbg(&self) -> Result<Vec<u8>> {
+        // Must track the number (and order) of `defmt::*!` calls in `mock-bin-src/src/main.rs`,
+        // since that's the real ELF the log view's `defmt_decoder::Table` resolves these indices
+        // against (via `attach mock-bin`) — add a log there, bump `DEFMT_END` here.
         const DEFMT_START: u16 = 1;
         const DEFMT_END: u16 = 6;
-        static DEFMT_IDX: AtomicU16 = AtomicU16::new(DEFMT_START);
-        static TIMESTAMP: AtomicU64 = AtomicU64::new(0);
+        sync::static_atomic!(DEFMT_IDX: AtomicU16 = AtomicU16::new(DEFMT_START));
+        sync::static_atomic!(TIMESTAMP: AtomicU64 = AtomicU64::new(0));
 
         let frame_idx = DEFMT_IDX.fetch_add(1, Ordering::Relaxed);
         let timestamp = TIMESTAMP.fetch_add(100000, Ordering::Relaxed);
@@ -1,5 +1,13 @@
+use crate::ring::Ring;
+use crate::sync::{self, Arc, AtomicBool, Condvar, Mutex, Ordering};
 use color_eyre::{Result, eyre::eyre};
-use std::sync::{atomic, mpsc};
+use futures_core::Stream;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use strum::{Display, EnumString};
 
 #[cfg(not(feature = "mock"))]
 unsafe extern "C" {
@@ -18,10 +26,15 @@ mod mock {
         0
     }
 
-    pub(super) unsafe fn WaitForNotification(event: u32) -> u32 {
-        // Just wait for a little bit then return the event that was passed in
+    pub(super) unsafe fn WaitForNotification(_event: u32) -> u32 {
+        // Just wait for a little bit then pretend the EC raised a debug frame notification -
+        // the one real consumer (`Debug`) needs a steady stream of these to have anything to
+        // show, and the single dispatcher thread always waits for `Event::Any` now rather than
+        // a caller-specific code, so there's no longer an input value worth echoing back. The
+        // mock only ever runs against `PlatformEventMap::default`, so this hardcodes that map's
+        // `DbgFrameAvailable` code rather than taking a `PlatformEventMap` to look it up in.
         std::thread::sleep(std::time::Duration::from_millis(500));
-        event
+        20
     }
 
     pub(super) unsafe fn CleanupNotification() {
@@ -32,85 +45,300 @@ mod mock {
 const RX_BUF_SZ: usize = 128;
 
 /// A notification event
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Display, EnumString)]
 pub enum Event {
     Any,
     DbgFrameAvailable,
+    AcAdapter,
+    BatteryTrippoint,
+    ThermalThreshold,
+    Lid,
+    Dock,
 }
 
-// Eventually would want to make this configurable to support multiple platforms
-// But for now hardcode values
-impl From<Event> for u32 {
-    fn from(event: Event) -> Self {
-        match event {
-            Event::Any => 0,
-            Event::DbgFrameAvailable => 20,
+/// Maps symbolic [`Event`]s to the platform-specific notification codes `WaitForNotification`
+/// actually returns, replacing what used to be a single hardcoded `impl From<Event> for u32` -
+/// that only ever matched one EC platform, so different platforms had no way to plug in their
+/// own codes without a recompile.
+pub struct PlatformEventMap {
+    codes: HashMap<Event, u32>,
+    events: HashMap<u32, Event>,
+}
+
+impl Default for PlatformEventMap {
+    fn default() -> Self {
+        Self::from_codes([
+            (Event::Any, 0),
+            (Event::DbgFrameAvailable, 20),
+            (Event::AcAdapter, 21),
+            (Event::BatteryTrippoint, 22),
+            (Event::ThermalThreshold, 23),
+            (Event::Lid, 24),
+            (Event::Dock, 25),
+        ])
+    }
+}
+
+impl PlatformEventMap {
+    /// Builds a map directly from `(Event, code)` pairs.
+    pub fn from_codes(codes: impl IntoIterator<Item = (Event, u32)>) -> Self {
+        let codes: HashMap<Event, u32> = codes.into_iter().collect();
+        let events = codes.iter().map(|(&event, &code)| (code, event)).collect();
+        Self { codes, events }
+    }
+
+    /// Parses a small `Event=code` per line config format, e.g.:
+    ///
+    /// ```text
+    /// DbgFrameAvailable=20
+    /// BatteryTrippoint=22
+    /// ```
+    ///
+    /// Blank lines and lines starting with `#` are ignored. Events not listed keep their
+    /// [`Default`] code, so a platform config only needs to override what differs.
+    pub fn from_config(config: &str) -> Result<Self> {
+        let mut map = Self::default();
+        for line in config.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, code) =
+                line.split_once('=').ok_or_else(|| eyre!("Malformed platform event map line: {line}"))?;
+            let event =
+                Event::from_str(name.trim()).map_err(|_| eyre!("Unknown event name: {}", name.trim()))?;
+            let code: u32 =
+                code.trim().parse().map_err(|_| eyre!("Invalid notification code: {}", code.trim()))?;
+
+            map.codes.insert(event, code);
         }
+        map.events = map.codes.iter().map(|(&event, &code)| (code, event)).collect();
+        Ok(map)
+    }
+
+    /// The platform-specific code `WaitForNotification` uses for `event`.
+    fn code(&self, event: Event) -> u32 {
+        *self.codes.get(&event).expect("PlatformEventMap must cover every Event variant")
+    }
+
+    /// Decodes a raw code received from `WaitForNotification` back into an [`Event`].
+    fn event(&self, code: u32) -> Result<Event> {
+        self.events.get(&code).copied().ok_or_else(|| eyre!("Unknown event code received: {code}"))
+    }
+}
+
+/// Flow-control result from [`EventRx::receive`]: `Read` carries the oldest unread entry plus how
+/// many newer entries were refused (dropped) because the buffer was already full since the last
+/// `Read`, `Pause` means nothing new has arrived yet, and `Dropped` means the dispatcher thread
+/// backing this receiver is gone for good (panicked), so no further data will ever show up.
+pub enum RecvStatus<T> {
+    Read { value: T, dropped: usize },
+    Pause,
+    Dropped,
+}
+
+// Set false by the dispatcher thread (via `AliveGuard`) right before it exits, on any path
+// including a panic, so `EventRx::receive` can tell a permanently-empty buffer from one that's
+// just paused.
+struct AliveGuard(Arc<AtomicBool>);
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
     }
 }
 
-impl TryFrom<u32> for Event {
-    type Error = color_eyre::Report;
-    fn try_from(value: u32) -> Result<Self> {
-        match value {
-            0 => Ok(Self::Any),
-            20 => Ok(Self::DbgFrameAvailable),
-            _ => Err(eyre!("Unknown event received")),
+/// Start/stop gate for an [`EventRx`]: `set_running(false)` makes its subscriber's `dispatch`
+/// drop matching events instead of buffering them, `set_running(true)` resumes delivery. `pub` so
+/// `tests/loom_notifications.rs` can drive it directly, and still built on a `Condvar` (rather
+/// than a plain `AtomicBool`) so the blocking `wait_until_running` it had when it gated a
+/// per-receiver waiter thread remains there and model-checked, even though [`Notifications`]'
+/// single dispatcher thread no longer needs to block on it.
+pub struct RunGate {
+    running: Mutex<bool>,
+    signal: Condvar,
+}
+
+impl RunGate {
+    pub fn new(running: bool) -> Self {
+        Self { running: Mutex::new(running), signal: Condvar::new() }
+    }
+
+    pub fn set_running(&self, running: bool) {
+        *self.running.lock().expect("Guard must not be poisoned") = running;
+        if running {
+            self.signal.notify_one();
+        }
+    }
+
+    /// Blocks the calling (waiter) thread until the gate is running.
+    pub fn wait_until_running(&self) {
+        let mut running = self.running.lock().expect("Guard must not be poisoned");
+        while !*running {
+            running = self.signal.wait(running).expect("Guard must not be poisoned");
         }
     }
+
+    /// Non-blocking check of the current state.
+    pub fn is_running(&self) -> bool {
+        *self.running.lock().expect("Guard must not be poisoned")
+    }
 }
 
 pub struct EventRx<T> {
-    rx: std::sync::mpsc::Receiver<T>,
-    signal_with_guard: std::sync::Arc<(std::sync::Mutex<bool>, std::sync::Condvar)>,
+    ring: Arc<Ring<T, RX_BUF_SZ>>,
+    alive: Arc<AtomicBool>,
+    gate: Arc<RunGate>,
+    waker: Arc<Mutex<Option<Waker>>>,
 }
 
 impl<T> EventRx<T> {
     /// Start the event receiver
     pub fn start(&mut self) {
-        let (guard, signal) = &*self.signal_with_guard;
-        *guard.lock().expect("Guard must not be poisoned") = true;
-        signal.notify_one();
+        self.gate.set_running(true);
     }
 
     /// Stop the event receiver
     pub fn stop(&mut self) {
-        let (guard, _signal) = &*self.signal_with_guard;
-        *guard.lock().expect("Guard must not be poisoned") = false;
+        self.gate.set_running(false);
+    }
+
+    /// Returns the oldest unread notification data, if any, along with flow-control status.
+    pub fn receive(&self) -> RecvStatus<T> {
+        match self.ring.pop() {
+            Some((value, dropped)) => RecvStatus::Read { value, dropped },
+            None if self.alive.load(Ordering::Acquire) => RecvStatus::Pause,
+            None => RecvStatus::Dropped,
+        }
     }
 
-    /// Returns the most recent data in the rx buffer if any
-    pub fn receive(&self) -> Option<T> {
-        match self.rx.try_recv() {
-            Ok(data) => Some(data),
-            Err(mpsc::TryRecvError::Empty) => None,
+    /// Adapts this receiver into a [`Stream`] so callers can `.await` events instead of polling
+    /// [`Self::receive`]. Consumes `self` since the ring buffer behind it is single-consumer
+    /// (SPSC): a stream and a `receive()`-based poller can't soundly share one `EventRx`.
+    pub fn into_stream(self) -> EventStream<T> {
+        EventStream(self)
+    }
+}
+
+/// A [`Stream`] adapter over [`EventRx`], obtained via [`EventRx::into_stream`].
+///
+/// `poll_next` pops the ring buffer and, when it's empty, registers the current task's waker so
+/// the dispatcher thread backing this receiver can wake it after the next matching push.
+/// `start()`/`stop()` carry over from the underlying `EventRx`: while stopped, matching events are
+/// dropped instead of pushed, so the stream simply reports `Poll::Pending` until `start()` lets
+/// it resume. Each item carries the same dropped-count `EventRx::receive` does, so a `Stream`
+/// caller can still report how many entries the ring refused since the last one it read.
+pub struct EventStream<T>(EventRx<T>);
 
-            // Choose to panic here for caller ergonomics
-            // This case shouldn't happen in this app and is pretty much unrecoverable
-            Err(mpsc::TryRecvError::Disconnected) => panic!("Polled dropped notification service"),
+impl<T> EventStream<T> {
+    /// Start the event receiver backing this stream.
+    pub fn start(&mut self) {
+        self.0.start();
+    }
+
+    /// Stop the event receiver backing this stream.
+    pub fn stop(&mut self) {
+        self.0.stop();
+    }
+
+    /// Returns a future resolving to the next item (or `None` once the stream is exhausted), for
+    /// `while let Some(..) = stream.next().await` callers. Hand-rolled because this crate depends
+    /// on `futures_core` (for the `Stream` trait) but not `futures_util` (for `StreamExt::next`).
+    pub fn next(&mut self) -> Next<'_, T> {
+        Next(self)
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = (T, usize);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.0.receive() {
+            RecvStatus::Read { value, dropped } => Poll::Ready(Some((value, dropped))),
+            RecvStatus::Dropped => Poll::Ready(None),
+            RecvStatus::Pause => {
+                *self.0.waker.lock().expect("Waker must not be poisoned") = Some(cx.waker().clone());
+
+                // The dispatcher thread may have pushed between our first `receive()` and
+                // registering the waker above; check once more so that push isn't missed.
+                match self.0.receive() {
+                    RecvStatus::Read { value, dropped } => Poll::Ready(Some((value, dropped))),
+                    RecvStatus::Dropped => Poll::Ready(None),
+                    RecvStatus::Pause => Poll::Pending,
+                }
+            }
         }
     }
 }
 
+/// Future backing [`EventStream::next`].
+pub struct Next<'a, T>(&'a mut EventStream<T>);
+
+impl<T> Future for Next<'_, T> {
+    type Output = Option<(T, usize)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.get_mut().0).poll_next(cx)
+    }
+}
+
+/// Drives `fut` once against a no-op [`Waker`] and returns its output if it completed.
+///
+/// This crate runs no executor - [`Debug`](crate::debug::Debug) and [`Battery`](crate::battery::Battery)
+/// just want to drain an [`EventStream`] once per tick via `while let Some(..) = stream.next().await`,
+/// and a "pending" outcome there only ever means "nothing to drain this tick" rather than
+/// something actually worth blocking a real task on.
+pub fn poll_once<F: Future>(fut: F) -> Option<F::Output> {
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, |_| {}, |_| {}, |_| {});
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = std::pin::pin!(fut);
+    match fut.as_mut().poll(&mut cx) {
+        Poll::Ready(out) => Some(out),
+        Poll::Pending => None,
+    }
+}
+
+/// A single registered [`Notifications::subscribe`] interest: `filter` decides whether an
+/// incoming [`Event`] is this subscriber's, and `dispatch` (which closes over that subscriber's
+/// own ring/waker/gate) pushes the mapped value and wakes any waiting task. Type-erased since the
+/// dispatcher holds every subscriber, regardless of `T`, in one `Vec`.
+struct Subscriber {
+    filter: Box<dyn Fn(Event) -> bool + Send>,
+    dispatch: Box<dyn Fn(Event) + Send>,
+}
+
 /// Singleton notification service
-static INITIALIZED: atomic::AtomicBool = atomic::AtomicBool::new(false);
-pub struct Notifications;
+sync::static_atomic!(INITIALIZED: AtomicBool = AtomicBool::new(false));
+pub struct Notifications {
+    alive: Arc<AtomicBool>,
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
 
 impl Notifications {
-    /// Create and initialize a new notification service.
+    /// Create and initialize a new notification service, decoding raw notification codes
+    /// according to `event_map`.
     ///
     /// Returns an error if notification service instance already exists.
-    pub fn new() -> Result<Self> {
-        if INITIALIZED
-            .compare_exchange(false, true, atomic::Ordering::SeqCst, atomic::Ordering::SeqCst)
-            .is_ok()
-        {
+    pub fn new(event_map: PlatformEventMap) -> Result<Self> {
+        if INITIALIZED.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
             // SAFETY: Only a single instance will ever exist at once
             let res = unsafe { InitializeNotification() };
             if res == 0 {
-                Ok(Self)
+                let alive = Arc::new(AtomicBool::new(true));
+                let subscribers: Arc<Mutex<Vec<Subscriber>>> = Arc::new(Mutex::new(Vec::new()));
+                Self::spawn_dispatcher(Arc::clone(&alive), Arc::clone(&subscribers), Arc::new(event_map));
+                Ok(Self { alive, subscribers })
             } else {
-                INITIALIZED.store(false, atomic::Ordering::SeqCst);
+                INITIALIZED.store(false, Ordering::SeqCst);
                 Err(eyre!("Failed to initialize notification service"))
             }
         } else {
@@ -118,51 +346,85 @@ impl Notifications {
         }
     }
 
-    /// Creates an event receiver `EventRx` which spawns a thread that waits for specified event.
-    ///
-    /// This receiver will then use the provided closure to perform some action and return data whenever event is received.
+    /// Claims the `INITIALIZED` singleton slot without spawning the dispatcher thread, so
+    /// `tests/loom_notifications.rs` can model-check the compare-exchange race `new` guards
+    /// itself with - the real dispatcher loops forever by design and can't be model-checked
+    /// (see that test module's doc comment).
+    #[cfg(loom)]
+    pub fn try_claim_singleton() -> bool {
+        INITIALIZED.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+
+    /// Releases a slot claimed by [`Self::try_claim_singleton`].
+    #[cfg(loom)]
+    pub fn release_singleton() {
+        INITIALIZED.store(false, Ordering::SeqCst);
+    }
+
+    /// Registers interest in events matching `filter`, mapping each one through `map` before it's
+    /// stored in the returned [`EventRx`]'s buffer.
     ///
-    /// This returned data is automatically stored in a buffer which caller can access via `EventRx::receive`.
-    pub fn event_receiver<T: Send + 'static>(
+    /// Unlike the one-thread-per-event-type approach this replaced, every subscriber is served by
+    /// a single dispatcher thread that calls `WaitForNotification(Event::Any)` once, decodes the
+    /// result, and fans it out to whichever subscribers' `filter` matches - so adding a new kind
+    /// of EC event only means adding another `subscribe` call, not another blocked OS thread.
+    pub fn subscribe<T: Send + 'static>(
         &self,
-        event: Event,
-        f: impl Fn(Event) -> T + Send + 'static,
+        filter: impl Fn(Event) -> bool + Send + 'static,
+        map: impl Fn(Event) -> T + Send + 'static,
     ) -> EventRx<T> {
-        let (tx, rx) = mpsc::sync_channel::<T>(RX_BUF_SZ);
-        let signal_with_guard = std::sync::Arc::new((std::sync::Mutex::new(false), std::sync::Condvar::new()));
-        let waiter = std::sync::Arc::clone(&signal_with_guard);
+        let ring = Arc::new(Ring::<T, RX_BUF_SZ>::default());
+        let gate = Arc::new(RunGate::new(false));
+        let waker: Arc<Mutex<Option<Waker>>> = Arc::new(Mutex::new(None));
 
-        std::thread::spawn(move || {
-            let (guard, signal) = &*waiter;
+        let sub_ring = Arc::clone(&ring);
+        let sub_gate = Arc::clone(&gate);
+        let sub_waker = Arc::clone(&waker);
 
-            loop {
-                // Check if we should still run, and if not, sleep until told to start again
-                {
-                    let mut running = guard.lock().expect("Guard must not be poisoned");
-                    while !*running {
-                        running = signal.wait(running).expect("Guard must not be poisoned");
-                    }
+        let dispatch: Box<dyn Fn(Event) + Send> = Box::new(move |event| {
+            // A stopped receiver just drops matching events on the floor rather than buffering
+            // them, mirroring the old per-receiver waiter thread parking instead of calling
+            // `wait_event` while stopped.
+            if sub_gate.is_running() {
+                sub_ring.push(map(event));
+                if let Some(waker) = sub_waker.lock().expect("Waker must not be poisoned").take() {
+                    waker.wake();
                 }
+            }
+        });
 
-                // If we somehow receive a notification that we didn't intend, just discard it
-                if let Ok(event) = Self::wait_event(event) {
-                    let data = f(event);
+        self.subscribers
+            .lock()
+            .expect("Subscribers must not be poisoned")
+            .push(Subscriber { filter: Box::new(filter), dispatch });
 
-                    // Receiver has dropped, so just end the thread silently
-                    if tx.send(data).is_err() {
-                        break;
+        EventRx { ring, alive: Arc::clone(&self.alive), gate, waker }
+    }
+
+    fn spawn_dispatcher(
+        alive: Arc<AtomicBool>,
+        subscribers: Arc<Mutex<Vec<Subscriber>>>,
+        event_map: Arc<PlatformEventMap>,
+    ) {
+        sync::thread::spawn(move || {
+            let _alive_guard = AliveGuard(alive);
+
+            loop {
+                // If we somehow receive an event we don't recognize, just discard it
+                if let Ok(event) = Self::wait_event(Event::Any, &event_map) {
+                    let subscribers = subscribers.lock().expect("Subscribers must not be poisoned");
+                    for subscriber in subscribers.iter().filter(|s| (s.filter)(event)) {
+                        (subscriber.dispatch)(event);
                     }
                 }
             }
         });
-
-        EventRx { rx, signal_with_guard }
     }
 
-    fn wait_event(event: Event) -> Result<Event> {
+    fn wait_event(event: Event, event_map: &PlatformEventMap) -> Result<Event> {
         // SAFETY: Driver can handle multiple threads calling simultaneously
-        let recv = unsafe { WaitForNotification(event.into()) };
-        Event::try_from(recv)
+        let recv = unsafe { WaitForNotification(event_map.code(event)) };
+        event_map.event(recv)
     }
 }
 
@@ -170,6 +432,6 @@ impl Drop for Notifications {
     fn drop(&mut self) {
         // SAFETY: This is only called once automatically when singleton service is dropped
         unsafe { CleanupNotification() };
-        INITIALIZED.store(false, atomic::Ordering::SeqCst);
+        INITIALIZED.store(false, Ordering::SeqCst);
     }
 }
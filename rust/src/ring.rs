@@ -0,0 +1,91 @@
+//! A reusable single-producer/single-consumer ring buffer backing [`crate::notifications::EventRx`].
+//!
+//! Unlike an `mpsc::sync_channel`, it needs no per-receiver allocation (it's just a fixed array
+//! behind the `Arc` already shared with the waiter thread) and never blocks: a full buffer
+//! refuses (and counts) the incoming entry instead of stalling the producer thread. An earlier
+//! version of this tried to overwrite the oldest entry on a full buffer instead, but that made
+//! `push` a second writer of `head` - racing `pop`'s read of `slots[head % N]` - so `head` is now
+//! advanced only by `pop`, full stop.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct Ring<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    // `head` is advanced only by `pop` (the consumer); `tail` is advanced only by `push` (the
+    // producer). `push` never touches `head`, even when the buffer is full - it just refuses the
+    // write and counts it as dropped, so a slot `pop` may be mid-read of is never raced. `tail`/
+    // `head` are monotonically increasing and indexed mod `N`, rather than wrapping at `N`
+    // directly, so "full" and "empty" (both `head == tail` mod `N`) stay distinguishable.
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+// SAFETY: `slots` is only ever touched from `push`/`pop`, which hand off ownership of each slot
+// via `Acquire`/`Release` on `head`/`tail`, so the producer and consumer never race on a slot.
+unsafe impl<T: Send, const N: usize> Sync for Ring<T, N> {}
+
+impl<T, const N: usize> Default for Ring<T, N> {
+    fn default() -> Self {
+        Self {
+            slots: std::array::from_fn(|_| UnsafeCell::new(MaybeUninit::uninit())),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<T, const N: usize> Ring<T, N> {
+    /// Producer-only. Writes `value` into the next slot, or - if the buffer is already full -
+    /// drops `value` and counts it, rather than overwriting the oldest entry and racing `pop`.
+    pub fn push(&self, value: T) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+
+        if tail.wrapping_sub(head) >= N {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        // SAFETY: this slot was last owned by a `pop` that has already happened-before (its
+        // `head` store is Acquire-loaded above), or was never written; either way only the
+        // producer touches it until `tail` is published below.
+        unsafe { (*self.slots[tail % N].get()).write(value) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Consumer-only. Takes the oldest unread entry (if any), along with how many entries
+    /// `push` has refused (because the buffer was full) since the last successful `pop`.
+    pub fn pop(&self) -> Option<(T, usize)> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // SAFETY: `tail`'s Acquire load pairs with the Release store at the end of `push`, so
+        // that write has happened-before this read; only the consumer reads this slot.
+        let value = unsafe { (*self.slots[head % N].get()).assume_init_read() };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+
+        Some((value, self.dropped.swap(0, Ordering::Relaxed)))
+    }
+}
+
+impl<T, const N: usize> Drop for Ring<T, N> {
+    fn drop(&mut self) {
+        // `&mut self` means no concurrent `push`/`pop` can be in flight, so plain loads suffice.
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+
+        for i in head..tail {
+            // SAFETY: every slot in `[head, tail)` was written by `push` and never read back out
+            // by `pop` (that would have advanced `head` past it), so it's still init.
+            unsafe { (*self.slots[i % N].get()).assume_init_drop() };
+        }
+    }
+}
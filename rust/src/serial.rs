@@ -0,0 +1,234 @@
+use crate::clock::SimTime;
+use crate::{Source, Threshold};
+use color_eyre::{Result, eyre::eyre};
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_millis(200);
+
+// End-of-frame delimiter used by rzcobs-encoded defmt frames on the wire, same as `mock_defmt_wire`
+const FRAME_DELIM: u8 = 0x00;
+
+/// Register codes for the small request/response protocol this transport speaks to the EC.
+/// Only covers what `Source` needs; anything the real firmware exposes beyond this is out of scope here.
+#[derive(Copy, Clone)]
+#[repr(u8)]
+enum Reg {
+    Temperature = 0x01,
+    Rpm = 0x02,
+    MinRpm = 0x03,
+    MaxRpm = 0x04,
+    Threshold = 0x05,
+    SetRpm = 0x06,
+    Bst = 0x07,
+    Bix = 0x08,
+    SetBtp = 0x09,
+}
+
+struct Inner {
+    port: Box<dyn serialport::SerialPort>,
+    // Bytes read off the wire that haven't completed a `defmt` frame yet
+    frame_buf: Vec<u8>,
+}
+
+/// A [`Source`] backed by a real EC reachable over a serial/USB link.
+///
+/// Unlike [`crate::mock::Mock`], which synthesizes frames with `mock_defmt_wire`, this reads
+/// actual rzcobs-framed `defmt` output the same way firmware emits it, and issues small
+/// request/reply packets over the same link for battery and thermal register reads. No UCSI
+/// registers are read.
+#[derive(Clone)]
+pub struct Serial {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Serial {
+    pub fn new(path: &str, baud_rate: u32) -> Result<Self> {
+        let port = serialport::new(path, baud_rate)
+            .timeout(REQUEST_TIMEOUT)
+            .open()
+            .map_err(|e| eyre!("Failed to open serial port {path}: {e}"))?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                port,
+                frame_buf: Vec::new(),
+            })),
+        })
+    }
+
+    // Writes a single-byte register request and reads back a fixed-size reply
+    fn request<const N: usize>(&self, reg: Reg) -> Result<[u8; N]> {
+        let mut inner = self.inner.lock().expect("Serial port must not be poisoned");
+        inner
+            .port
+            .write_all(&[reg as u8])
+            .map_err(|e| eyre!("Failed to write request: {e}"))?;
+
+        let mut reply = [0u8; N];
+        inner
+            .port
+            .read_exact(&mut reply)
+            .map_err(|e| eyre!("Failed to read reply: {e}"))?;
+        Ok(reply)
+    }
+
+    // Reads one byte at a time until (and excluding) a `0x00` terminator, for the string fields
+    // in a `Bix` reply. Lossy-converts instead of failing outright, since a garbled string field
+    // shouldn't take down the whole battery-info read.
+    fn read_cstring(inner: &mut Inner) -> Result<String> {
+        let mut bytes = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            inner
+                .port
+                .read_exact(&mut byte)
+                .map_err(|e| eyre!("Failed to read reply: {e}"))?;
+            if byte[0] == 0 {
+                break;
+            }
+            bytes.push(byte[0]);
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl Source for Serial {
+    // Real hardware reports its own live readings, so `now` (needed only to make [`crate::mock::Mock`]'s
+    // synthetic waveforms reproducible) is irrelevant here and goes unused.
+    fn get_temperature(&self, _now: SimTime) -> Result<f64> {
+        let reply = self.request::<8>(Reg::Temperature)?;
+        Ok(f64::from_le_bytes(reply))
+    }
+
+    fn get_rpm(&self, _now: SimTime) -> Result<f64> {
+        let reply = self.request::<8>(Reg::Rpm)?;
+        Ok(f64::from_le_bytes(reply))
+    }
+
+    fn get_min_rpm(&self) -> Result<f64> {
+        let reply = self.request::<8>(Reg::MinRpm)?;
+        Ok(f64::from_le_bytes(reply))
+    }
+
+    fn get_max_rpm(&self) -> Result<f64> {
+        let reply = self.request::<8>(Reg::MaxRpm)?;
+        Ok(f64::from_le_bytes(reply))
+    }
+
+    fn get_threshold(&self, threshold: Threshold) -> Result<f64> {
+        let mut inner = self.inner.lock().expect("Serial port must not be poisoned");
+        inner
+            .port
+            .write_all(&[Reg::Threshold as u8, threshold as u8])
+            .map_err(|e| eyre!("Failed to write request: {e}"))?;
+
+        let mut reply = [0u8; 8];
+        inner
+            .port
+            .read_exact(&mut reply)
+            .map_err(|e| eyre!("Failed to read reply: {e}"))?;
+        Ok(f64::from_le_bytes(reply))
+    }
+
+    fn set_rpm(&self, rpm: f64) -> Result<()> {
+        let mut inner = self.inner.lock().expect("Serial port must not be poisoned");
+        let mut req = vec![Reg::SetRpm as u8];
+        req.extend(rpm.to_le_bytes());
+        inner
+            .port
+            .write_all(&req)
+            .map_err(|e| eyre!("Failed to write request: {e}"))
+    }
+
+    // Reply is four little-endian u32s, in `BstData` field order.
+    fn get_bst(&self, _now: SimTime) -> Result<crate::battery::BstData> {
+        let reply = self.request::<16>(Reg::Bst)?;
+        let mut fields = reply.chunks_exact(4).map(|c| u32::from_le_bytes(c.try_into().unwrap()));
+        let mut next = || fields.next().expect("reply has exactly 4 u32 fields");
+
+        Ok(crate::battery::BstData {
+            state: crate::battery::ChargeState::try_from(next())?,
+            rate: next(),
+            capacity: next(),
+            voltage: next(),
+        })
+    }
+
+    // Reply is the 16 numeric `BixData` fields (in field order) as little-endian u32s, followed
+    // by `swap_cap` as a 17th u32, followed by the four string fields each as a NUL-terminated
+    // byte run (there's no length prefix, so `read_cstring` just reads until it sees a `0x00`).
+    fn get_bix(&self) -> Result<crate::battery::BixData> {
+        let mut inner = self.inner.lock().expect("Serial port must not be poisoned");
+        inner
+            .port
+            .write_all(&[Reg::Bix as u8])
+            .map_err(|e| eyre!("Failed to write request: {e}"))?;
+
+        const NUMERIC_FIELDS: usize = 17;
+        let mut numeric = [0u8; NUMERIC_FIELDS * 4];
+        inner
+            .port
+            .read_exact(&mut numeric)
+            .map_err(|e| eyre!("Failed to read reply: {e}"))?;
+        let mut fields = numeric.chunks_exact(4).map(|c| u32::from_le_bytes(c.try_into().unwrap()));
+        let mut next = || fields.next().expect("reply has exactly NUMERIC_FIELDS u32 fields");
+
+        Ok(crate::battery::BixData {
+            revision: next(),
+            power_unit: crate::battery::PowerUnit::try_from(next())?,
+            design_capacity: next(),
+            last_full_capacity: next(),
+            battery_technology: crate::battery::BatteryTechnology::try_from(next())?,
+            design_voltage: next(),
+            warning_capacity: next(),
+            low_capacity: next(),
+            cycle_count: next(),
+            accuracy: next(),
+            max_sample_time: next(),
+            min_sample_time: next(),
+            max_average_interval: next(),
+            min_average_interval: next(),
+            capacity_gran1: next(),
+            capacity_gran2: next(),
+            swap_cap: crate::battery::SwapCap::try_from(next())?,
+            model_number: Self::read_cstring(&mut inner)?,
+            serial_number: Self::read_cstring(&mut inner)?,
+            battery_type: Self::read_cstring(&mut inner)?,
+            oem_info: Self::read_cstring(&mut inner)?,
+        })
+    }
+
+    fn set_btp(&self, trippoint: u32) -> Result<()> {
+        let mut inner = self.inner.lock().expect("Serial port must not be poisoned");
+        let mut req = vec![Reg::SetBtp as u8];
+        req.extend(trippoint.to_le_bytes());
+        inner
+            .port
+            .write_all(&req)
+            .map_err(|e| eyre!("Failed to write request: {e}"))
+    }
+
+    // Reads whatever bytes are currently available off the wire and hands back the next
+    // complete rzcobs frame (up to and including the `0x00` delimiter), buffering any partial
+    // trailing bytes for the next call. Returns an empty frame if nothing has completed yet.
+    fn get_dbg(&self) -> Result<Vec<u8>> {
+        let mut inner = self.inner.lock().expect("Serial port must not be poisoned");
+
+        let available = inner.port.bytes_to_read().unwrap_or(0) as usize;
+        if available > 0 {
+            let mut chunk = vec![0u8; available];
+            inner
+                .port
+                .read_exact(&mut chunk)
+                .map_err(|e| eyre!("Failed to read from serial port: {e}"))?;
+            inner.frame_buf.extend(chunk);
+        }
+
+        match inner.frame_buf.iter().position(|&b| b == FRAME_DELIM) {
+            Some(end) => Ok(inner.frame_buf.drain(..=end).collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::clock::SimTime;
+use crate::{Source, Threshold};
+use color_eyre::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Non-blocking counterpart to [`Source`], for transports where every read or write may have to
+/// wait on I/O (e.g. a real EC over serial/RTT) and can't be allowed to stall the caller.
+///
+/// Method-for-method it mirrors `Source`, but a blocking `Source` creates-and-confirms (a call
+/// is guaranteed to have taken effect by the time it returns), whereas an `AsyncSource` call
+/// only fires the request off; any confirmation arrives whenever its future is next polled.
+pub trait AsyncSource {
+    fn get_temperature(&self, now: SimTime) -> impl Future<Output = Result<f64>> + Send;
+    fn get_rpm(&self, now: SimTime) -> impl Future<Output = Result<f64>> + Send;
+    fn get_min_rpm(&self) -> impl Future<Output = Result<f64>> + Send;
+    fn get_max_rpm(&self) -> impl Future<Output = Result<f64>> + Send;
+    fn get_threshold(&self, threshold: Threshold) -> impl Future<Output = Result<f64>> + Send;
+    fn set_rpm(&self, rpm: f64) -> impl Future<Output = Result<()>> + Send;
+    fn get_bst(&self, now: SimTime) -> impl Future<Output = Result<crate::battery::BstData>> + Send;
+    fn get_bix(&self) -> impl Future<Output = Result<crate::battery::BixData>> + Send;
+    fn set_btp(&self, trippoint: u32) -> impl Future<Output = Result<()>> + Send;
+    fn get_dbg(&self) -> impl Future<Output = Result<Vec<u8>>> + Send;
+}
+
+/// A transport that can act as both a blocking [`Source`] and a non-blocking [`AsyncSource`]
+/// over the same underlying link, e.g. a real hardware connection that supports synchronous
+/// request/confirm calls for setup and non-blocking polling on the hot path.
+///
+/// Blanket-implemented for any type that already implements both halves, so a transport only
+/// needs to provide `Source` and `AsyncSource` and automatically becomes a `Client`.
+pub trait Client: Source + AsyncSource {}
+impl<T: Source + AsyncSource> Client for T {}
+
+// Result slot shared between `BlockingCall`'s spawned thread and whoever polls it.
+struct Shared<T> {
+    result: Mutex<Option<T>>,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// A [`Future`] that resolves once a [`std::thread::spawn`]'d blocking call finishes, backing the
+/// blanket [`AsyncSource`] impl below. This crate has no async executor, so "off the render path"
+/// here just means "on its own OS thread" rather than cooperatively scheduled.
+struct BlockingCall<T>(Arc<Shared<T>>);
+
+impl<T: Send + 'static> BlockingCall<T> {
+    fn spawn(f: impl FnOnce() -> T + Send + 'static) -> Self {
+        let shared = Arc::new(Shared { result: Mutex::new(None), waker: Mutex::new(None) });
+
+        let worker = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            let value = f();
+            *worker.result.lock().expect("Result must not be poisoned") = Some(value);
+            if let Some(waker) = worker.waker.lock().expect("Waker must not be poisoned").take() {
+                waker.wake();
+            }
+        });
+
+        Self(shared)
+    }
+}
+
+impl<T> Future for BlockingCall<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut result = this.0.result.lock().expect("Result must not be poisoned");
+        match result.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                *this.0.waker.lock().expect("Waker must not be poisoned") = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Upgrades any cloneable, thread-safe [`Source`] into an [`AsyncSource`] by running each call on
+/// its own thread and resolving once that thread finishes - so a slow transport (e.g. real serial
+/// I/O) can't stall whoever is awaiting it. Combined with the blanket [`Client`] impl above, this
+/// means every `Source` this crate has (`Mock`, `Serial`) is automatically a `Client` too, with no
+/// transport-specific async code required.
+impl<T: Source + Clone + Send + 'static> AsyncSource for T {
+    // Every call below goes through `<T as Source>::...` rather than `source.get_...(..)`,
+    // since plain method syntax would be ambiguous between this impl's own `Source` bound and the
+    // `AsyncSource` trait it's implementing.
+    fn get_temperature(&self, now: SimTime) -> impl Future<Output = Result<f64>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_temperature(&source, now))
+    }
+
+    fn get_rpm(&self, now: SimTime) -> impl Future<Output = Result<f64>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_rpm(&source, now))
+    }
+
+    fn get_min_rpm(&self) -> impl Future<Output = Result<f64>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_min_rpm(&source))
+    }
+
+    fn get_max_rpm(&self) -> impl Future<Output = Result<f64>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_max_rpm(&source))
+    }
+
+    fn get_threshold(&self, threshold: Threshold) -> impl Future<Output = Result<f64>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_threshold(&source, threshold))
+    }
+
+    fn set_rpm(&self, rpm: f64) -> impl Future<Output = Result<()>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::set_rpm(&source, rpm))
+    }
+
+    fn get_bst(&self, now: SimTime) -> impl Future<Output = Result<crate::battery::BstData>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_bst(&source, now))
+    }
+
+    fn get_bix(&self) -> impl Future<Output = Result<crate::battery::BixData>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_bix(&source))
+    }
+
+    fn set_btp(&self, trippoint: u32) -> impl Future<Output = Result<()>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::set_btp(&source, trippoint))
+    }
+
+    fn get_dbg(&self) -> impl Future<Output = Result<Vec<u8>>> + Send {
+        let source = self.clone();
+        BlockingCall::spawn(move || <T as Source>::get_dbg(&source))
+    }
+}
+
+/// Polls a single in-flight async call once per invocation, only starting a new one once the
+/// previous has resolved - so a caller on [`App`](crate::app::App)'s tick loop can keep asking
+/// for fresh data every tick without ever blocking on a call that's still in flight.
+pub struct AsyncPoll<T> {
+    in_flight: Option<Pin<Box<dyn Future<Output = T> + Send>>>,
+}
+
+impl<T> Default for AsyncPoll<T> {
+    fn default() -> Self {
+        Self { in_flight: None }
+    }
+}
+
+impl<T: 'static> AsyncPoll<T> {
+    /// Returns the result of the in-flight call once it resolves. If nothing is in flight, starts
+    /// one via `make` so a later tick has something to poll.
+    pub fn poll<F>(&mut self, make: impl FnOnce() -> F) -> Option<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let fut = self.in_flight.get_or_insert_with(|| Box::pin(make()));
+        match crate::notifications::poll_once(fut.as_mut()) {
+            Some(value) => {
+                self.in_flight = None;
+                Some(value)
+            }
+            None => None,
+        }
+    }
+}
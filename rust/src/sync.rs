@@ -0,0 +1,38 @@
+//! Thin aliasing layer over the atomics, locks and statics `Mock` and `Notifications` use for
+//! their shared state, so `tests/loom_mock.rs` and `tests/loom_notifications.rs` can swap in
+//! loom's instrumented equivalents (`cfg(loom)`) without touching any of the call sites in
+//! [`crate::mock`] or [`crate::notifications`].
+
+#[cfg(loom)]
+pub use loom::sync::atomic::{AtomicBool, AtomicI64, AtomicU16, AtomicU64, Ordering};
+#[cfg(not(loom))]
+pub use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU16, AtomicU64, Ordering};
+
+#[cfg(loom)]
+pub use loom::sync::{Arc, Condvar, Mutex};
+#[cfg(not(loom))]
+pub use std::sync::{Arc, Condvar, Mutex};
+
+#[cfg(loom)]
+pub use loom::thread;
+#[cfg(not(loom))]
+pub use std::thread;
+
+/// Declares a process-wide atomic static. Expands to `loom::lazy_static!` under `cfg(loom)`,
+/// since loom reconstructs its statics fresh for every interleaving it explores (a plain `static`
+/// would leak state across model-checker iterations), and to an ordinary `static` otherwise.
+#[cfg(loom)]
+macro_rules! static_atomic {
+    ($name:ident: $ty:ty = $init:expr) => {
+        loom::lazy_static! {
+            static ref $name: $ty = $init;
+        }
+    };
+}
+#[cfg(not(loom))]
+macro_rules! static_atomic {
+    ($name:ident: $ty:ty = $init:expr) => {
+        static $name: $ty = $init;
+    };
+}
+pub(crate) use static_atomic;
@@ -10,6 +10,7 @@ use ratatui::{
 };
 
 use crate::app::Module;
+use crate::clock::SimTime;
 
 const LABEL_COLOR: Color = tailwind::SLATE.c200;
 
@@ -21,7 +22,7 @@ impl Module for Ucsi {
         "UCSI Information".into()
     }
 
-    fn update(&mut self) {}
+    fn update(&mut self, _now: SimTime) {}
 
     fn handle_event(&mut self, _evt: &Event) {}
 
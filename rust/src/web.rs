@@ -0,0 +1,220 @@
+//! Browser frontend for [`App`], driving the same [`App::tick`]/[`App::on_event`] step functions
+//! `run` uses natively, just from a `requestAnimationFrame` loop and canvas/keyboard events
+//! instead of a TTY. Ships the ODP EC demo as a web page with no local build required.
+//!
+//! `std::time::Instant` panics on `wasm32-unknown-unknown`, so elapsed time here is measured off
+//! `Performance::now` instead — the same `wasm32` awareness [`crate::clock`] needs for its own
+//! femtosecond math.
+
+#![cfg(target_arch = "wasm32")]
+
+use crate::app::App;
+use crate::mock::Mock;
+use crate::notifications::{Notifications, PlatformEventMap};
+
+use ratatui::backend::{Backend, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Position, Size};
+use ratatui::Terminal;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, KeyboardEvent};
+
+const CELL_WIDTH: f64 = 9.0;
+const CELL_HEIGHT: f64 = 18.0;
+const FONT: &str = "16px monospace";
+
+/// A [`ratatui::backend::Backend`] that paints cells as monospace text onto an HTML canvas,
+/// instead of writing ANSI escapes to a TTY like [`ratatui::backend::CrosstermBackend`] does.
+struct CanvasBackend {
+    ctx: CanvasRenderingContext2d,
+    cols: u16,
+    rows: u16,
+}
+
+impl CanvasBackend {
+    fn new(canvas: &HtmlCanvasElement) -> Self {
+        let cols = (canvas.width() as f64 / CELL_WIDTH) as u16;
+        let rows = (canvas.height() as f64 / CELL_HEIGHT) as u16;
+
+        let ctx = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .expect("canvas 2d context")
+            .dyn_into::<CanvasRenderingContext2d>()
+            .expect("2d context");
+        ctx.set_font(FONT);
+
+        Self { ctx, cols, rows }
+    }
+}
+
+impl Backend for CanvasBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        for (x, y, cell) in content {
+            let px = x as f64 * CELL_WIDTH;
+            let py = y as f64 * CELL_HEIGHT;
+
+            self.ctx.set_fill_style(&JsValue::from_str(&color_to_css(cell.bg)));
+            self.ctx.fill_rect(px, py, CELL_WIDTH, CELL_HEIGHT);
+
+            self.ctx.set_fill_style(&JsValue::from_str(&color_to_css(cell.fg)));
+            let _ = self.ctx.fill_text(cell.symbol(), px, py + CELL_HEIGHT - 4.0);
+        }
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        Ok(Position::ORIGIN)
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, _position: P) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        let (w, h) = (self.cols as f64 * CELL_WIDTH, self.rows as f64 * CELL_HEIGHT);
+        self.ctx.set_fill_style(&JsValue::from_str("black"));
+        self.ctx.fill_rect(0.0, 0.0, w, h);
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        Ok(Size::new(self.cols, self.rows))
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        Ok(WindowSize {
+            columns_rows: Size::new(self.cols, self.rows),
+            pixels: Size::new(self.cols as u16 * CELL_WIDTH as u16, self.rows as u16 * CELL_HEIGHT as u16),
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn color_to_css(color: ratatui::style::Color) -> String {
+    use ratatui::style::Color;
+    match color {
+        Color::Reset => "black".to_string(),
+        Color::Black => "black".to_string(),
+        Color::Red => "red".to_string(),
+        Color::Green => "green".to_string(),
+        Color::Yellow => "yellow".to_string(),
+        Color::Blue => "blue".to_string(),
+        Color::Magenta => "magenta".to_string(),
+        Color::Cyan => "cyan".to_string(),
+        Color::Gray | Color::DarkGray => "gray".to_string(),
+        Color::White => "white".to_string(),
+        Color::Rgb(r, g, b) => format!("rgb({r},{g},{b})"),
+        _ => "white".to_string(),
+    }
+}
+
+/// Translates a DOM keydown into the same [`Event::Key`] values [`App::on_event`] already knows
+/// how to handle, so no dispatch logic has to be duplicated for the web frontend.
+fn key_event_from_dom(evt: &KeyboardEvent) -> Option<Event> {
+    let code = match evt.key().as_str() {
+        "ArrowLeft" => KeyCode::Left,
+        "ArrowRight" => KeyCode::Right,
+        "ArrowUp" => KeyCode::Up,
+        "ArrowDown" => KeyCode::Down,
+        "Enter" => KeyCode::Enter,
+        "Escape" => KeyCode::Esc,
+        "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        s if s.chars().count() == 1 => KeyCode::Char(s.chars().next()?),
+        _ => return None,
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if evt.shift_key() {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if evt.ctrl_key() {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+
+    Some(Event::Key(KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Press)))
+}
+
+/// Entry point called from JS: `run(canvas)`. Spawns the same `App` the native binary runs,
+/// wired to a [`Mock`] source since a browser has no EC to talk to, and drives it from a
+/// `requestAnimationFrame` loop instead of blocking on stdin.
+#[wasm_bindgen]
+pub fn run(canvas: HtmlCanvasElement) -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+
+    let notifications =
+        Notifications::new(PlatformEventMap::default()).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let app = Rc::new(RefCell::new(App::new(Mock::new(), &notifications)));
+    let terminal = Rc::new(RefCell::new(Terminal::new(CanvasBackend::new(&canvas))?));
+
+    {
+        let app = Rc::clone(&app);
+        let keydown = Closure::<dyn FnMut(KeyboardEvent)>::new(move |evt: KeyboardEvent| {
+            if let Some(event) = key_event_from_dom(&evt) {
+                app.borrow_mut().on_event(event);
+                evt.prevent_default();
+            }
+        });
+        canvas.set_onkeydown(Some(keydown.as_ref().unchecked_ref()));
+        keydown.forget();
+    }
+
+    let tick_rate_ms = 1000.0;
+    let performance = web_sys::window().expect("window").performance().expect("performance");
+    let last_tick = Rc::new(RefCell::new(performance.now()));
+
+    let frame = Rc::new(RefCell::new(None));
+    let frame_clone = Rc::clone(&frame);
+    *frame_clone.borrow_mut() = Some(Closure::<dyn FnMut()>::new(move || {
+        let now_ms = performance.now();
+        let elapsed_ms = now_ms - *last_tick.borrow();
+
+        if elapsed_ms >= tick_rate_ms {
+            app.borrow_mut().tick(std::time::Duration::from_millis(elapsed_ms as u64));
+            *last_tick.borrow_mut() = now_ms;
+        }
+
+        let _ = terminal.borrow_mut().draw(|f| f.render_widget(&*app.borrow(), f.area()));
+
+        if app.borrow().is_running() {
+            request_animation_frame(frame.borrow().as_ref().unwrap());
+        }
+    }));
+    request_animation_frame(frame_clone.borrow().as_ref().unwrap());
+
+    Ok(())
+}
+
+fn request_animation_frame(closure: &Closure<dyn FnMut()>) {
+    web_sys::window()
+        .expect("window")
+        .request_animation_frame(closure.as_ref().unchecked_ref())
+        .expect("requestAnimationFrame");
+}
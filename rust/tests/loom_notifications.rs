@@ -0,0 +1,126 @@
+//! Model-checks the synchronization primitives behind [`ec_demo::notifications`] under every
+//! thread interleaving loom can find.
+//!
+//! Only runs under `cfg(loom)` (`RUSTFLAGS="--cfg loom" LOOM_MAX_PREEMPTIONS=2 cargo test
+//! --test loom_notifications --release`), since loom replaces the real scheduler with an
+//! exhaustive one and is far too slow to run as part of the normal suite.
+//!
+//! `Notifications::subscribe`'s dispatcher thread loops forever by design (it only stops via a
+//! panic), so loom - which needs every spawned thread to terminate to finish exploring an
+//! interleaving - can't model-check it directly, and neither can `Notifications::new` itself
+//! (it spawns that same dispatcher). These tests instead cover the pieces that actually have the
+//! ordering requirements called out for this work: `RunGate`, the mutex/condvar handshake
+//! `start()`/`stop()` are built on (including `dispatch`'s `is_running()`-then-push sequence,
+//! which is exactly how a real notification can race a `stop()`), and the `INITIALIZED`
+//! compare-exchange guarding the `Notifications` singleton - the latter via
+//! `try_claim_singleton`/`release_singleton`, a `#[cfg(loom)]`-only pair that exercises the same
+//! compare-exchange as `new` without spawning the dispatcher thread behind it.
+
+#![cfg(loom)]
+
+use ec_demo::notifications::{Notifications, RunGate};
+use loom::sync::Arc;
+use loom::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn waiter_never_deadlocks_after_a_start() {
+    loom::model(|| {
+        let gate = Arc::new(RunGate::new(false));
+
+        let waiter = {
+            let gate = Arc::clone(&gate);
+            // Mirrors the real waiter loop's gating step: block until told to run, then return -
+            // standing in for one iteration of `wait_event` + `ring.push`, which terminate in
+            // practice but aren't loom-model-checkable themselves (real FFI/condvar wait).
+            loom::thread::spawn(move || gate.wait_until_running())
+        };
+        let starter = {
+            let gate = Arc::clone(&gate);
+            loom::thread::spawn(move || gate.set_running(true))
+        };
+
+        starter.join().unwrap();
+        waiter.join().unwrap();
+    });
+}
+
+#[test]
+fn stop_then_start_cannot_drop_a_pending_wakeup() {
+    loom::model(|| {
+        // Gate starts running, same as a freshly-started `EventRx`.
+        let gate = Arc::new(RunGate::new(true));
+
+        let waiter = {
+            let gate = Arc::clone(&gate);
+            loom::thread::spawn(move || {
+                // First pass-through: already running, returns immediately.
+                gate.wait_until_running();
+                // Second pass-through: may race a concurrent stop()/start().
+                gate.wait_until_running();
+            })
+        };
+        let driver = {
+            let gate = Arc::clone(&gate);
+            loom::thread::spawn(move || {
+                gate.set_running(false);
+                gate.set_running(true);
+            })
+        };
+
+        driver.join().unwrap();
+        waiter.join().unwrap();
+    });
+}
+
+#[test]
+fn dispatch_racing_stop_never_double_delivers() {
+    loom::model(|| {
+        // Mirrors `Notifications::subscribe`'s dispatch closure, which only pushes (and wakes a
+        // waiting task) if the gate is observed running - exactly the shape of a real
+        // notification racing a concurrent `stop()` the request called out.
+        let gate = Arc::new(RunGate::new(true));
+        let delivered = Arc::new(AtomicUsize::new(0));
+
+        let dispatcher = {
+            let gate = Arc::clone(&gate);
+            let delivered = Arc::clone(&delivered);
+            loom::thread::spawn(move || {
+                if gate.is_running() {
+                    delivered.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        };
+        let stopper = {
+            let gate = Arc::clone(&gate);
+            loom::thread::spawn(move || gate.set_running(false))
+        };
+
+        dispatcher.join().unwrap();
+        stopper.join().unwrap();
+
+        // The race can land either way - delivered just before the stop took effect, or dropped
+        // just after - but it must land exactly one way, never double-counted.
+        assert!(delivered.load(Ordering::Relaxed) <= 1);
+    });
+}
+
+#[test]
+fn initialized_flag_resets_after_racing_construction() {
+    loom::model(|| {
+        let t1 = loom::thread::spawn(Notifications::try_claim_singleton);
+        let t2 = loom::thread::spawn(Notifications::try_claim_singleton);
+
+        let r1 = t1.join().unwrap();
+        let r2 = t2.join().unwrap();
+
+        // Exactly one of the two racing claims wins the compare-exchange.
+        assert_ne!(r1, r2);
+        if r1 || r2 {
+            Notifications::release_singleton();
+        }
+
+        // Whichever one won has now released it, so a fresh claim must succeed.
+        assert!(Notifications::try_claim_singleton());
+        Notifications::release_singleton();
+    });
+}
@@ -0,0 +1,50 @@
+//! Drives [`ec_demo::ring::Ring`] with genuine concurrent producer/consumer OS threads under
+//! load, rather than a loom model (which only explores a handful of interleavings and can't
+//! stand in for actually racing the real scheduler across hundreds of thousands of operations).
+//!
+//! This is the single-writer invariant the `unsafe impl Sync` in `ring.rs` depends on: if `push`
+//! ever raced `pop` over the same slot, this would be expected to corrupt values or crash under
+//! more than a few iterations.
+
+use ec_demo::ring::Ring;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+#[test]
+fn concurrent_push_pop_preserves_order_under_load() {
+    const COUNT: usize = 500_000;
+
+    let ring: Arc<Ring<usize, 16>> = Arc::new(Ring::default());
+    let producer_done = Arc::new(AtomicBool::new(false));
+
+    let producer = {
+        let ring = Arc::clone(&ring);
+        let producer_done = Arc::clone(&producer_done);
+        thread::spawn(move || {
+            for i in 0..COUNT {
+                ring.push(i);
+            }
+            producer_done.store(true, Ordering::Release);
+        })
+    };
+
+    let mut last = None;
+    let mut received = 0usize;
+    loop {
+        match ring.pop() {
+            Some((value, _dropped)) => {
+                if let Some(last) = last {
+                    assert!(value > last, "consumer must see pushes in order, got {value} after {last}");
+                }
+                last = Some(value);
+                received += 1;
+            }
+            None if producer_done.load(Ordering::Acquire) => break,
+            None => thread::yield_now(),
+        }
+    }
+
+    producer.join().unwrap();
+    assert!(received > 0, "consumer should have observed at least some pushes");
+}